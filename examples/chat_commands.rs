@@ -0,0 +1,65 @@
+use computercraft::{
+    wrappers::ap::{
+        chat_box::{ChatBox, Command, CommandError, CommandRouter},
+        rs_bridge::{ItemFilter, RsBridge},
+    },
+    Server,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let chat_box: ChatBox = computer
+        .find_peripheral("chatBox_0")
+        .await?
+        .as_wrapper()
+        .await?;
+    let bridge: RsBridge = computer
+        .find_peripheral("rsBridge_0")
+        .await?
+        .as_wrapper()
+        .await?;
+    let bridge = &bridge;
+
+    let router = CommandRouter::new("!")
+        .register(
+            Command::new("stock", move |ctx| async move {
+                let item: String = ctx.args.get(0)?;
+                let item = bridge
+                    .get_item(ItemFilter::from_name(format!("minecraft:{item}")))
+                    .await
+                    .map_err(CommandError::failed)?;
+
+                Ok(match item {
+                    Some(item) => format!("{} x{}", item.display_name, item.amount),
+                    None => "None stored".to_string(),
+                })
+            })
+            .usage("<item>")
+            .description("How many of an item are stored"),
+        )
+        .register(
+            Command::new("craft", move |ctx| async move {
+                let item: String = ctx.args.get(0)?;
+                let count: usize = ctx.args.optional(1)?.unwrap_or(1);
+                let filter = ItemFilter::from_name(format!("minecraft:{item}")).with_count(count);
+
+                match bridge.craft_item(filter).await {
+                    Ok(true) => Ok(format!("Crafting {count} {item}")),
+                    Ok(false) => Err(CommandError::failed("Couldn't start crafting")),
+                    Err(err) => Err(CommandError::failed(err)),
+                }
+            })
+            .usage("<item> [count]")
+            .description("Crafts an item")
+            // Notch's UUID
+            .allow(["069a79f4-44e9-4726-a5be-fca90e38aaf5"]),
+        );
+
+    router.run(&chat_box).await?;
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+use computercraft::{
+    wrappers::ap::{
+        colony_integrator::ColonyIntegrator,
+        request_fulfiller::{ExportTarget, RequestFulfiller},
+        rs_bridge::RsBridge,
+    },
+    Server,
+};
+use futures_util::StreamExt;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let colony: ColonyIntegrator = computer
+        .find_peripheral("colonyIntegrator_0")
+        .await?
+        .as_wrapper()
+        .await?;
+    let bridge: RsBridge = computer
+        .find_peripheral("rsBridge_0")
+        .await?
+        .as_wrapper()
+        .await?;
+
+    // only report what would be delivered, pass `false` once the output looks right
+    let fulfiller = RequestFulfiller::new(ExportTarget::Peripheral("minecraft:chest_0".into()))
+        .craft_missing(true)
+        .dry_run(true);
+
+    let mut reports = fulfiller.run(&colony, &bridge);
+    while let Some(report) = reports.next().await {
+        for result in report?.results {
+            println!(
+                "{} x{}: {:?} {:?}",
+                result.request.name, result.request.count, result.exported, result.outcome
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use computercraft::{
+    api::redstone::Side,
+    wrappers::ap::player_detector::{BlockPos, Geofence, GeofenceEvent, PlayerDetector, Region},
+    Server,
+};
+use futures_util::StreamExt;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let detector: PlayerDetector = computer
+        .find_peripheral("playerDetector_0")
+        .await?
+        .as_wrapper()
+        .await?;
+
+    let geofence = Geofence::new(&detector)
+        .with_region(
+            Region::new(
+                "front door",
+                BlockPos::new(100, 64, 200),
+                BlockPos::new(104, 68, 204),
+            )
+            .in_dimension("minecraft:overworld"),
+        )
+        .with_region(Region::new(
+            "vault",
+            BlockPos::new(90, 40, 190),
+            BlockPos::new(110, 50, 210),
+        ));
+
+    let redstone = computer.redstone();
+
+    // open the door while someone is in front of it, and report anyone entering the vault
+    let mut at_door = 0;
+    let mut events = geofence.run();
+    while let Some(event) = events.next().await {
+        match event? {
+            GeofenceEvent::Enter { region, .. } if region == "front door" => {
+                at_door += 1;
+                redstone.set_output(Side::Back, true).await?;
+            }
+            GeofenceEvent::Leave { region, .. } if region == "front door" => {
+                at_door -= 1;
+                redstone.set_output(Side::Back, at_door > 0).await?;
+            }
+            GeofenceEvent::Enter { username, region } => {
+                println!("{username} entered the {region}")
+            }
+            GeofenceEvent::Leave { username, region } => println!("{username} left the {region}"),
+        }
+    }
+
+    Ok(())
+}
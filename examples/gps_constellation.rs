@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use computercraft::{
+    api::gps::{host_gps_constellation, Position},
+    Server,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let positions = [
+        Position::new(0.0, 200.0, 0.0),
+        Position::new(8.0, 200.0, 0.0),
+        Position::new(0.0, 200.0, 8.0),
+        Position::new(0.0, 208.0, 0.0),
+    ];
+
+    let mut hosts = Vec::new();
+    for (i, position) in positions.into_iter().enumerate() {
+        let computer = server
+            .wait_for_connection_from(&format!("gps_{}", i + 1))
+            .await?;
+        hosts.push((computer, position));
+    }
+    let client = server.wait_for_connection_from("client").await?;
+
+    let ids = hosts
+        .iter()
+        .map(|(computer, _)| computer.computer_info().map(|info| info.id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the handles only resolve once hosting stops, which it won't until the hosts reboot
+    let _handles = host_gps_constellation(hosts.clone());
+    println!(
+        "hosting, client is at {:?}",
+        client.gps_locate(Duration::from_secs(2)).await?
+    );
+
+    // the positions were saved on the hosts, so they carry on hosting after a reboot
+    for (computer, _) in &hosts {
+        // the computer goes away before it can reply
+        let _ = computer.os().reboot().await;
+    }
+    drop(hosts);
+    for id in ids.into_iter().flatten() {
+        server.wait_for_connection_with_id(id).await?;
+    }
+
+    println!(
+        "rebooted, client is at {:?}",
+        client.gps_locate(Duration::from_secs(2)).await?
+    );
+
+    Ok(())
+}
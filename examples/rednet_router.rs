@@ -0,0 +1,24 @@
+use computercraft::{
+    api::rednet::{RednetRouter, RouteRule},
+    Server,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    // relay every "chat" message between all connected workers,
+    // and forward "alarm" messages received by "base" to "outpost"
+    let router = RednetRouter::new()
+        .with_rule(RouteRule::new().protocol("chat"))
+        .with_rule(
+            RouteRule::new()
+                .protocol("alarm")
+                .from_worker("base")
+                .to_workers(["outpost"]),
+        );
+
+    router.run(&server).await?;
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use computercraft::{api::redstone::Side, Server};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let redstone = computer.redstone();
+
+    let mut events = redstone.events().await?;
+
+    // mirror the input on the left side to the output on the right side
+    loop {
+        let on = redstone.get_input(Side::Left).await?;
+        redstone.set_output(Side::Right, on).await?;
+
+        events.recv().await?;
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use computercraft::{
+    wrappers::ap::{
+        rs_bridge::{ItemFilter, RsBridge},
+        stock_keeper::{StockKeeper, StockRule},
+    },
+    Server,
+};
+use futures_util::StreamExt;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let bridge: RsBridge = computer
+        .find_peripheral("rsBridge_0")
+        .await?
+        .as_wrapper()
+        .await?;
+
+    let keeper = StockKeeper::new()
+        .interval(Duration::from_secs(30))
+        .with_rule(StockRule::new(ItemFilter::from_name("minecraft:torch"), 256).with_batch_size(4))
+        .with_rule(
+            StockRule::new(ItemFilter::from_tag("minecraft:planks"), 128)
+                .craft_as(ItemFilter::from_name("minecraft:oak_planks"))
+                .with_batch_size(4)
+                .with_max_craft(64),
+        );
+
+    let mut events = keeper.run(&bridge);
+    while let Some(event) = events.next().await {
+        println!("{:?}", event?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use computercraft::{
+    wrappers::{
+        inventory::{Inventory, ItemQuery, StorageSystem},
+        modem::Modem,
+    },
+    Server,
+};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let server = Server::listen();
+
+    let computer = server.wait_for_connection().await?;
+
+    let modem: Modem = computer.find_peripheral("back").await?.as_wrapper().await?;
+
+    // every chest on the network except the one used to put items in and take them out
+    let io: Inventory = computer
+        .find_peripheral("minecraft:chest_0")
+        .await?
+        .as_wrapper()
+        .await?;
+    let mut storage = StorageSystem::from_network(&computer, &modem, &[io.address()]).await?;
+
+    let inserted = storage.insert(&io).await?;
+    println!("inserted {inserted} items");
+
+    for item in storage.items() {
+        println!("{} x{}", item.name, item.count);
+    }
+
+    let extracted = storage
+        .extract(&ItemQuery::from_name("minecraft:cobblestone"), 64, &io)
+        .await?;
+    println!("extracted {extracted} cobblestone");
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 require "worker.controller"
 require "worker.config"
+require "worker.files"
 
 local args = { ... }
 
@@ -7,4 +8,16 @@ local config = Config(args)
 
 local controller = Controller(config)
 
-controller:start()
+-- saved by the host's `host_gps`, so a GPS host carries on hosting after a reboot
+local gpsHost = settings.get("computercraft-rs.gps_host")
+
+if type(gpsHost) == "table" then
+    parallel.waitForAll(
+        function() controller:start() end,
+        function()
+            shell.run("gps", "host", tostring(gpsHost[1]), tostring(gpsHost[2]), tostring(gpsHost[3]))
+        end
+    )
+else
+    controller:start()
+end
@@ -1,11 +1,16 @@
 require "worker.serialize"
 
+-- responses with this id are events that were not requested by the host
+local EVENT_ID = "00000000-0000-0000-0000-000000000000"
+
 Controller = {
     ws = nil,
     url = nil,
     name = nil,
     reconnect = true,
     debug = false,
+    tasks = nil,
+    subscriptions = nil,
 }
 
 function Controller.__init__(base, config)
@@ -20,6 +25,8 @@ function Controller.__init__(base, config)
         name = config.name,
         reconnect = config.reconnect,
         debug = config.debug,
+        tasks = {},
+        subscriptions = {},
     }
     setmetatable(self, { __index = Controller })
     return self
@@ -35,6 +42,10 @@ function Controller:connect()
             sleep(5)
         end
     end
+
+    -- anything left over belongs to the previous connection
+    self.tasks = {}
+    self.subscriptions = {}
 end
 
 function Controller:__debug(msg)
@@ -55,12 +66,75 @@ function Controller:__get_computer_info()
     end
 
     return {
+        id = os.getComputerID(),
         name = self.name,
         kind = ty,
         advanced = term.isColor(),
     }
 end
 
+function Controller:__call(kind, fn, prefix, args)
+    local ty = type(args)
+
+    if ty == "nil" then
+        args = {}
+    elseif ty == "number" or ty == "string" or ty == "boolean" then
+        args = { args }
+    elseif ty ~= "table" then
+        return {
+            kind = kind,
+            data = {
+                success = false,
+                error = { "Invalid argument type. Must be nil, number, string, boolean, or array." },
+                result = nil,
+            }
+        }
+    end
+
+    -- `#args` isn't reliable when there are nils (JSON nulls) between arguments
+    local n = 0
+    for k in pairs(args) do
+        if type(k) == "number" and k > n then
+            n = k
+        end
+    end
+
+    local call_args = { table.unpack(prefix) }
+    for i = 1, n do
+        call_args[#prefix + i] = args[i]
+    end
+
+    -- pack the returns so that `nil, "some error"` style returns keep their shape
+    local returns = table.pack(pcall(fn, table.unpack(call_args, 1, #prefix + n)))
+    local success = returns[1]
+    local result = nil
+    if returns.n > 1 then
+        result = { table.unpack(returns, 2, returns.n) }
+        if next(result) == nil then
+            result = nil
+        end
+    end
+    if success then
+        return {
+            kind = kind,
+            data = {
+                success = true,
+                error = nil,
+                result = result,
+            }
+        }
+    else
+        return {
+            kind = kind,
+            data = {
+                success = false,
+                error = result,
+                result = nil,
+            }
+        }
+    end
+end
+
 function Controller:__handle_request(request)
     if request.kind == "Echo" then
         return request
@@ -78,67 +152,55 @@ function Controller:__handle_request(request)
     elseif request.kind == "CallPeripheral" then
         local address = request.data.address
         local method = request.data.method
-        local args = request.data.args
-        local ty = type(args)
-
-        if ty == "nil" then
-            args = {}
-        elseif ty == "number" or ty == "string" or ty == "boolean" then
-            args = { args }
-        elseif ty ~= "table" then
-            return {
-                kind = request.kind,
-                data = {
-                    success = false,
-                    error = "Invalid argument type. Must be nil, number, string, boolean, or array.",
-                    result = nil,
-                }
-            }
-        end
-
-        local returns = { pcall(peripheral.call, address, method, table.unpack(args)) }
-        local success = table.remove(returns, 1)
-        local result = returns
-        if #result == 0 then
-            result = nil
-        end
-        if success then
-            return {
-                kind = request.kind,
-                data = {
-                    success = true,
-                    error = nil,
-                    result = result,
-                }
-            }
-        else
+        return self:__call(request.kind, peripheral.call, { address, method }, request.data.args)
+    elseif request.kind == "CallGlobal" then
+        local api = _ENV[request.data.api]
+        local method = request.data.method
+        if type(api) ~= "table" or type(api[method]) ~= "function" then
             return {
                 kind = request.kind,
                 data = {
                     success = false,
-                    error = result,
+                    error = { string.format("No such global function: %s.%s", request.data.api, method) },
                     result = nil,
                 }
             }
         end
+        return self:__call(request.kind, api[method], {}, request.data.args)
+    elseif request.kind == "SubscribeEvent" then
+        local name = request.data
+        local new = not self.subscriptions[name]
+        self.subscriptions[name] = true
+        return {
+            kind = request.kind,
+            data = new,
+        }
     elseif request.kind == "GetPeripheralType" then
         local address = request.data
         return {
             kind = request.kind,
-            data = peripheral.getType(address),
+            data = { peripheral.getType(address) },
         }
     end
 end
 
-function Controller:poll()
-    local msg = self.ws.receive()
+function Controller:__send(res)
+    local ok, ser = pcall(serializeJSON, res)
+    if not ok then
+        print("failed to serialize message: " .. tostring(ser))
+        return false, ser
+    end
 
-    if msg == nil then
-        -- the socket has closed, we're done here
-        self.ws = nil -- trying to use this socket will error
-        return false
+    self:__debug("sending message: " .. ser)
+
+    if self.ws then
+        pcall(self.ws.send, ser)
     end
 
+    return true
+end
+
+function Controller:__handle_message(msg)
     self:__debug("received message: " .. msg)
     msg = textutils.unserializeJSON(msg, { parse_empty_array = false })
 
@@ -146,14 +208,100 @@ function Controller:poll()
     local request = msg.request
 
     local res_data = self:__handle_request(request)
-    local res = {
+    local ok, err = self:__send({
         id = id,
         response = res_data,
-    }
-    local ser = serializeJSON(res)
-    self:__debug("sending message: " .. ser)
+    })
+
+    if not ok and type(res_data.data) == "table" and res_data.data.success ~= nil then
+        -- the call went through but returned something we can't send (i.e. a function),
+        -- so report that instead of leaving the host waiting forever
+        self:__send({
+            id = id,
+            response = {
+                kind = res_data.kind,
+                data = {
+                    success = false,
+                    error = { "Failed to serialize return values: " .. tostring(err) },
+                    result = nil,
+                },
+            },
+        })
+    end
+end
+
+function Controller:__send_event(event)
+    local params = { table.unpack(event, 2, event.n) }
+    if next(params) == nil then
+        params = empty_json_array
+    end
+
+    self:__send({
+        id = EVENT_ID,
+        response = {
+            kind = "Event",
+            data = {
+                name = event[1],
+                params = params,
+            },
+        },
+    })
+end
 
-    self.ws.send(ser)
+-- requests are handled in their own coroutines (much like the parallel API) so that
+-- a request which yields (i.e. waiting on a timer) does not swallow the events that
+-- other requests and the websocket depend on
+function Controller:__resume(task, ...)
+    local ok, filter = coroutine.resume(task.co, ...)
+    if not ok then
+        printError(filter)
+    else
+        task.filter = filter
+    end
+end
+
+function Controller:__spawn(fn)
+    local task = { co = coroutine.create(fn), filter = nil }
+    self:__resume(task)
+    if coroutine.status(task.co) ~= "dead" then
+        table.insert(self.tasks, task)
+    end
+end
+
+function Controller:__dispatch(event)
+    for i = #self.tasks, 1, -1 do
+        local task = self.tasks[i]
+        if task.filter == nil or task.filter == event[1] or event[1] == "terminate" then
+            self:__resume(task, table.unpack(event, 1, event.n))
+        end
+        if coroutine.status(task.co) == "dead" then
+            table.remove(self.tasks, i)
+        end
+    end
+end
+
+function Controller:poll()
+    local event = table.pack(os.pullEventRaw())
+    local name = event[1]
+
+    if name == "terminate" then
+        error("Terminated", 0)
+    end
+
+    self:__dispatch(event)
+
+    if name == "websocket_message" and event[2] == self.url then
+        local msg = event[3]
+        self:__spawn(function() self:__handle_message(msg) end)
+    elseif name == "websocket_closed" and event[2] == self.url then
+        -- the socket has closed, we're done here
+        self.ws = nil -- trying to use this socket will error
+        return false
+    end
+
+    if self.subscriptions[name] then
+        self:__send_event(event)
+    end
 
     return true
 end
@@ -0,0 +1,31 @@
+-- File handles can't be sent to the host, so the host reads and writes whole files
+-- through these helpers instead of calling fs.open directly.
+
+WorkerFiles = {}
+
+function WorkerFiles.read(path)
+    local file, err = fs.open(path, "r")
+    if not file then
+        error(err, 0)
+    end
+
+    local contents = file.readAll()
+    file.close()
+
+    return contents or ""
+end
+
+function WorkerFiles.write(path, contents, append)
+    local mode = "w"
+    if append then
+        mode = "a"
+    end
+
+    local file, err = fs.open(path, mode)
+    if not file then
+        error(err, 0)
+    end
+
+    file.write(contents)
+    file.close()
+end
@@ -0,0 +1,153 @@
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+};
+
+use serde_json::Value;
+
+use crate::{
+    computer::{deserialize_all, Computer, ComputerKind},
+    debug_feature,
+    error::{Error, Result},
+    event::EventReceiver,
+    lua_compat::LuaVec,
+};
+
+use super::Api;
+
+mod block_info;
+pub use block_info::*;
+
+/// The `commands` API, only available on command computers.
+#[derive(Debug, Clone)]
+pub struct Commands<'a> {
+    inner: Api<'a>,
+}
+
+impl Computer {
+    pub fn commands(&self) -> Result<Commands<'_>> {
+        match self.computer_info()?.kind {
+            ComputerKind::Command => Ok(Commands {
+                inner: self.api("commands"),
+            }),
+            _ => debug_feature!(Err(Error::NotACommandComputer)),
+        }
+    }
+}
+
+impl<'a> Commands<'a> {
+    /// Runs a command (without the leading `/`) and waits for it to finish.
+    pub async fn exec(&self, command: impl ToString) -> Result<CommandOutput> {
+        self.inner
+            .call_method_with_raw("exec", command.to_string())
+            .await
+    }
+
+    /// Starts running a command without waiting for it to finish. Await the returned
+    /// task to get the command's output from the `task_complete` event.
+    pub async fn exec_async(&self, command: impl ToString) -> Result<CommandTask> {
+        let events = self.inner.computer.subscribe("task_complete").await?;
+        let id = self
+            .inner
+            .call_method_with("execAsync", command.to_string())
+            .await?;
+
+        Ok(CommandTask { id, events })
+    }
+
+    /// Lists the available commands, or the sub-commands of `path` (i.e. `["gamerule"]`).
+    pub async fn list(&self, path: &[&str]) -> Result<Vec<String>> {
+        let commands: LuaVec<String> = self
+            .inner
+            .call_method_with(
+                "list",
+                path.iter().map(|part| part.to_string()).collect::<Vec<_>>(),
+            )
+            .await?;
+
+        Ok(commands.0)
+    }
+
+    pub async fn get_block_position(&self) -> Result<(i64, i64, i64)> {
+        self.inner
+            .call_method_with_raw("getBlockPosition", Value::Null)
+            .await
+    }
+
+    /// Gets information about the block at the given position, in the computer's dimension
+    /// unless `dimension` is given.
+    pub async fn get_block_info(
+        &self,
+        (x, y, z): (i64, i64, i64),
+        dimension: Option<&str>,
+    ) -> Result<BlockInfo> {
+        self.inner
+            .call_method_with("getBlockInfo", (x, y, z, dimension.map(str::to_string)))
+            .await
+    }
+
+    /// Gets information about every block in the given (inclusive) region, ordered by x, then z, then y.
+    pub async fn get_block_infos(
+        &self,
+        (min_x, min_y, min_z): (i64, i64, i64),
+        (max_x, max_y, max_z): (i64, i64, i64),
+        dimension: Option<&str>,
+    ) -> Result<Vec<BlockInfo>> {
+        let infos: LuaVec<BlockInfo> = self
+            .inner
+            .call_method_with(
+                "getBlockInfos",
+                (
+                    min_x,
+                    min_y,
+                    min_z,
+                    max_x,
+                    max_y,
+                    max_z,
+                    dimension.map(str::to_string),
+                ),
+            )
+            .await?;
+
+        Ok(infos.0)
+    }
+}
+
+/// A command started with [`Commands::exec_async`].
+#[derive(Debug)]
+pub struct CommandTask {
+    id: u64,
+    events: EventReceiver,
+}
+
+impl CommandTask {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    pub async fn wait(mut self) -> Result<CommandOutput> {
+        loop {
+            let event = self.events.recv().await?;
+            if event.param::<u64>(0)? != self.id {
+                continue;
+            }
+
+            // task_complete is (id, task succeeded, ...task results or error)
+            let results = event.params.get(2..).unwrap_or_default().to_vec();
+            return if event.param::<bool>(1)? {
+                deserialize_all(results)
+            } else {
+                debug_feature!(Err(Error::LuaError(results)))
+            };
+        }
+    }
+}
+
+impl IntoFuture for CommandTask {
+    type Output = Result<CommandOutput>;
+    type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.wait())
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+/// The value of a block state property (i.e. `facing = "north"` or `lit = true`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BlockStateValue {
+    Bool(bool),
+    Int(i64),
+    String(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInfo {
+    pub name: String,
+    pub state: HashMap<String, BlockStateValue>,
+    pub tags: HashMap<String, bool>,
+    pub nbt: Option<Value>,
+}
+
+/// The result of running a command.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandOutput {
+    pub success: bool,
+    #[serde(deserialize_with = "crate::lua_compat::deserialize_with")]
+    pub output: Vec<String>,
+    /// The number of things the command affected, if it reports one.
+    #[serde(default)]
+    pub affected: Option<i64>,
+}
@@ -0,0 +1,165 @@
+use crate::{computer::Computer, error::Result, lua_compat::LuaVec};
+
+use super::Api;
+
+/// The `fs` API. Paths are relative to [`Fs::root`], which is the root of the computer
+/// unless the API was scoped with [`Fs::scoped`] (i.e. to a disk's mount path).
+///
+/// Scoping is only a convenience, paths containing `..` can still leave the root.
+#[derive(Debug, Clone)]
+pub struct Fs<'a> {
+    inner: Api<'a>,
+    files: Api<'a>,
+    root: String,
+}
+
+impl Computer {
+    pub fn fs(&self) -> Fs<'_> {
+        Fs {
+            inner: self.api("fs"),
+            files: self.api("WorkerFiles"),
+            root: String::new(),
+        }
+    }
+}
+
+impl<'a> Fs<'a> {
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Creates a new API where every path is relative to `root`, which is itself relative to the current root.
+    pub fn scoped(&self, root: impl AsRef<str>) -> Fs<'a> {
+        Fs {
+            inner: self.inner.clone(),
+            files: self.files.clone(),
+            root: self.path(root),
+        }
+    }
+
+    fn path(&self, path: impl AsRef<str>) -> String {
+        let path = path.as_ref().trim_matches('/');
+        match (self.root.is_empty(), path.is_empty()) {
+            (true, _) => path.to_string(),
+            (false, true) => self.root.clone(),
+            (false, false) => format!("{}/{}", self.root, path),
+        }
+    }
+
+    pub async fn exists(&self, path: impl AsRef<str>) -> Result<bool> {
+        self.inner.call_method_with("exists", self.path(path)).await
+    }
+
+    pub async fn is_dir(&self, path: impl AsRef<str>) -> Result<bool> {
+        self.inner.call_method_with("isDir", self.path(path)).await
+    }
+
+    pub async fn is_read_only(&self, path: impl AsRef<str>) -> Result<bool> {
+        self.inner
+            .call_method_with("isReadOnly", self.path(path))
+            .await
+    }
+
+    /// Lists the names of the files and directories in `path`.
+    pub async fn list(&self, path: impl AsRef<str>) -> Result<Vec<String>> {
+        let names: LuaVec<String> = self.inner.call_method_with("list", self.path(path)).await?;
+
+        Ok(names.0)
+    }
+
+    pub async fn get_size(&self, path: impl AsRef<str>) -> Result<u64> {
+        self.inner
+            .call_method_with("getSize", self.path(path))
+            .await
+    }
+
+    pub async fn get_free_space(&self, path: impl AsRef<str>) -> Result<u64> {
+        self.inner
+            .call_method_with("getFreeSpace", self.path(path))
+            .await
+    }
+
+    /// The capacity of the drive `path` is on, or `None` for read-only drives.
+    pub async fn get_capacity(&self, path: impl AsRef<str>) -> Result<Option<u64>> {
+        self.inner
+            .call_method_with_optional("getCapacity", self.path(path))
+            .await
+    }
+
+    /// The name of the drive `path` is on (i.e. `hdd`, `rom` or the side of a disk drive).
+    pub async fn get_drive(&self, path: impl AsRef<str>) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getDrive", self.path(path))
+            .await
+    }
+
+    pub async fn make_dir(&self, path: impl AsRef<str>) -> Result<()> {
+        self.inner.call_method("makeDir", self.path(path)).await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, path: impl AsRef<str>) -> Result<()> {
+        self.inner.call_method("delete", self.path(path)).await?;
+
+        Ok(())
+    }
+
+    pub async fn copy(&self, from: impl AsRef<str>, to: impl AsRef<str>) -> Result<()> {
+        self.inner
+            .call_method("copy", (self.path(from), self.path(to)))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn move_file(&self, from: impl AsRef<str>, to: impl AsRef<str>) -> Result<()> {
+        self.inner
+            .call_method("move", (self.path(from), self.path(to)))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn read_to_string(&self, path: impl AsRef<str>) -> Result<String> {
+        self.files.call_method_with("read", self.path(path)).await
+    }
+
+    /// Writes `contents` to the file at `path`, replacing it if it exists.
+    pub async fn write(&self, path: impl AsRef<str>, contents: impl ToString) -> Result<()> {
+        self.files
+            .call_method("write", (self.path(path), contents.to_string(), false))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn append(&self, path: impl AsRef<str>, contents: impl ToString) -> Result<()> {
+        self.files
+            .call_method("write", (self.path(path), contents.to_string(), true))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Copies every file and directory in this API's root into the root of `to`, which must be
+    /// on the same computer and must not already contain files with the same names.
+    pub async fn copy_all_to(&self, to: &Fs<'_>) -> Result<()> {
+        for name in self.list("").await? {
+            self.inner
+                .call_method("copy", (self.path(&name), to.path(&name)))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes everything in this API's root, without deleting the root itself.
+    pub async fn clear(&self) -> Result<()> {
+        for name in self.list("").await? {
+            self.delete(name).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,428 @@
+use std::{
+    ops::{Add, Mul, Sub},
+    time::Duration,
+};
+
+use serde_json::Value;
+use tokio::task::JoinHandle;
+
+use crate::{computer::Computer, error::Result};
+
+#[cfg(feature = "peripheral-wrappers")]
+use crate::wrappers::modem::Modem;
+
+/// The channel GPS hosts listen on and reply from.
+pub const CHANNEL_GPS: u16 = 65534;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Position {
+    pub fn new(x: f64, y: f64, z: f64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn dot(self, other: Position) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Position) -> Position {
+        Position::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    pub fn normalize(self) -> Position {
+        self * (1.0 / self.length())
+    }
+
+    /// Rounds each component to the nearest multiple of `tolerance`.
+    pub fn round(self, tolerance: f64) -> Position {
+        let round = |n: f64| (n / tolerance + 0.5).floor() * tolerance;
+        Position::new(round(self.x), round(self.y), round(self.z))
+    }
+}
+
+impl Add for Position {
+    type Output = Position;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Position::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Position {
+    type Output = Position;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Position::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl Mul<f64> for Position {
+    type Output = Position;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Position::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl Computer {
+    /// Locates the computer with `gps.locate`, returning `None` if it could not be determined
+    /// before `timeout` elapsed.
+    pub async fn gps_locate(&self, timeout: Duration) -> Result<Option<Position>> {
+        match &self
+            .api("gps")
+            .call_method("locate", timeout.as_secs_f64())
+            .await?[..]
+        {
+            [Value::Number(x), Value::Number(y), Value::Number(z)] => Ok(Some(Position::new(
+                x.as_f64().unwrap(),
+                y.as_f64().unwrap(),
+                z.as_f64().unwrap(),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// The distance to a GPS host at a known position.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GpsFix {
+    pub position: Position,
+    pub distance: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpsSolution {
+    Position(Position),
+    /// The fixes agree on two positions, usually because every host is on the same plane.
+    /// Another host off that plane is needed to tell them apart.
+    Ambiguous(Position, Position),
+    /// There are less than three fixes from distinct positions.
+    NotEnoughFixes,
+    /// Every fix is on the same line, so there are infinitely many solutions.
+    Degenerate,
+}
+
+/// Computes a position from GPS fixes on the host, the same way `gps.locate` does.
+#[derive(Debug, Clone, Default)]
+pub struct GpsSolver {
+    fixes: Vec<GpsFix>,
+}
+
+impl GpsSolver {
+    const TOLERANCE: f64 = 0.01;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fixes(&self) -> &[GpsFix] {
+        &self.fixes
+    }
+
+    /// Adds a fix, ignoring fixes from a position that has already been seen.
+    pub fn add_fix(&mut self, position: Position, distance: f64) {
+        if self.fixes.iter().any(|fix| fix.position == position) {
+            return;
+        }
+
+        self.fixes.push(GpsFix { position, distance });
+    }
+
+    pub fn solve(&self) -> GpsSolution {
+        if self.fixes.len() < 3 {
+            return GpsSolution::NotEnoughFixes;
+        }
+
+        // unlike gps.locate, look for any three fixes that aren't in a line
+        // instead of giving up when the first three are
+        let n = self.fixes.len();
+        let mut found = None;
+        'search: for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    if let Some(solution) =
+                        Self::trilaterate(&self.fixes[a], &self.fixes[b], &self.fixes[c])
+                    {
+                        found = Some(([a, b, c], solution));
+                        break 'search;
+                    }
+                }
+            }
+        }
+
+        let Some((used, mut solution)) = found else {
+            return GpsSolution::Degenerate;
+        };
+
+        for (i, fix) in self.fixes.iter().enumerate() {
+            if used.contains(&i) {
+                continue;
+            }
+            if let GpsSolution::Ambiguous(p1, p2) = solution {
+                solution = Self::narrow(p1, p2, fix);
+            } else {
+                break;
+            }
+        }
+
+        solution
+    }
+
+    fn trilaterate(a: &GpsFix, b: &GpsFix, c: &GpsFix) -> Option<GpsSolution> {
+        let a2b = b.position - a.position;
+        let a2c = c.position - a.position;
+
+        if a2b.normalize().dot(a2c.normalize()).abs() > 0.999 {
+            return None;
+        }
+
+        let d = a2b.length();
+        let ex = a2b.normalize();
+        let i = ex.dot(a2c);
+        let ey = (a2c - ex * i).normalize();
+        let j = ey.dot(a2c);
+        let ez = ex.cross(ey);
+
+        let (r1, r2, r3) = (a.distance, b.distance, c.distance);
+
+        let x = (r1.powi(2) - r2.powi(2) + d.powi(2)) / (2.0 * d);
+        let y = (r1.powi(2) - r3.powi(2) - x.powi(2) + (x - i).powi(2) + j.powi(2)) / (2.0 * j);
+
+        let result = a.position + ex * x + ey * y;
+
+        let z_squared = r1.powi(2) - x.powi(2) - y.powi(2);
+        if z_squared > 0.0 {
+            let z = z_squared.sqrt();
+            let p1 = (result + ez * z).round(Self::TOLERANCE);
+            let p2 = (result - ez * z).round(Self::TOLERANCE);
+            if p1 != p2 {
+                return Some(GpsSolution::Ambiguous(p1, p2));
+            }
+            return Some(GpsSolution::Position(p1));
+        }
+
+        Some(GpsSolution::Position(result.round(Self::TOLERANCE)))
+    }
+
+    fn narrow(p1: Position, p2: Position, fix: &GpsFix) -> GpsSolution {
+        let dist1 = ((p1 - fix.position).length() - fix.distance).abs();
+        let dist2 = ((p2 - fix.position).length() - fix.distance).abs();
+
+        if (dist1 - dist2).abs() < Self::TOLERANCE {
+            GpsSolution::Ambiguous(p1, p2)
+        } else if dist1 < dist2 {
+            GpsSolution::Position(p1.round(Self::TOLERANCE))
+        } else {
+            GpsSolution::Position(p2.round(Self::TOLERANCE))
+        }
+    }
+
+    /// Pings the GPS hosts in range of a wireless modem and solves for its position
+    /// from the replies received within `timeout`.
+    #[cfg(feature = "peripheral-wrappers")]
+    pub async fn locate_with(modem: &Modem<'_>, timeout: Duration) -> Result<GpsSolution> {
+        let mut solver = GpsSolver::new();
+
+        let was_open = modem.is_open(CHANNEL_GPS).await?;
+        if !was_open {
+            modem.open(CHANNEL_GPS).await?;
+        }
+
+        let received: Result<()> = async {
+            let mut messages = modem.messages().await?;
+            modem.transmit(CHANNEL_GPS, CHANNEL_GPS, "PING").await?;
+
+            let deadline = tokio::time::Instant::now() + timeout;
+            while let Ok(message) = tokio::time::timeout_at(deadline, messages.recv()).await {
+                let message = message?;
+                if message.channel != CHANNEL_GPS || message.reply_channel != CHANNEL_GPS {
+                    continue;
+                }
+                let (Ok([x, y, z]), Some(distance)) = (
+                    serde_json::from_value::<[f64; 3]>(message.payload),
+                    message.distance,
+                ) else {
+                    continue;
+                };
+
+                solver.add_fix(Position::new(x, y, z), distance);
+                if let GpsSolution::Position(_) = solver.solve() {
+                    break;
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+
+        // close the channel again even if receiving failed, but report that failure first
+        let closed = if was_open {
+            Ok(())
+        } else {
+            modem.close(CHANNEL_GPS).await
+        };
+        received?;
+        closed?;
+
+        Ok(solver.solve())
+    }
+}
+
+/// The settings key [`host_gps`] saves a GPS host's position under. The worker reads it when it
+/// starts and runs `gps host` alongside itself, so a host keeps hosting after a reboot.
+pub const GPS_HOST_SETTING: &str = "computercraft-rs.gps_host";
+
+/// Saves `position` in the computer's settings and starts `gps host` on it.
+///
+/// `gps host` runs alongside the worker until the computer shuts down, so the returned
+/// handle only resolves if hosting stops (i.e. there is no wireless modem) or the computer
+/// disconnects. Once the computer reboots, the worker starts hosting again by itself, so
+/// this shouldn't be called again for the same computer. Use [`stop_hosting_gps`] to undo this.
+pub fn host_gps(computer: Computer, position: Position) -> JoinHandle<Result<()>> {
+    tokio::spawn(async move {
+        let settings = computer.api("settings");
+        settings
+            .call_method(
+                "set",
+                (GPS_HOST_SETTING, vec![position.x, position.y, position.z]),
+            )
+            .await?;
+        settings.call_method("save", Value::Null).await?;
+
+        computer
+            .api("shell")
+            .call_method(
+                "run",
+                (
+                    "gps",
+                    "host",
+                    position.x.to_string(),
+                    position.y.to_string(),
+                    position.z.to_string(),
+                ),
+            )
+            .await?;
+
+        Ok(())
+    })
+}
+
+/// Configures every computer in `hosts` as a GPS host at the given position, see [`host_gps`].
+pub fn host_gps_constellation(
+    hosts: impl IntoIterator<Item = (Computer, Position)>,
+) -> Vec<JoinHandle<Result<()>>> {
+    hosts
+        .into_iter()
+        .map(|(computer, position)| host_gps(computer, position))
+        .collect()
+}
+
+/// Stops the computer from hosting GPS once it reboots. `gps host` can't be stopped while
+/// it is running, so reboot the computer to stop it right away.
+pub async fn stop_hosting_gps(computer: &Computer) -> Result<()> {
+    let settings = computer.api("settings");
+    settings.call_method("unset", GPS_HOST_SETTING).await?;
+    settings.call_method("save", Value::Null).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solver(target: Position, hosts: &[Position]) -> GpsSolver {
+        let mut solver = GpsSolver::new();
+        for &host in hosts {
+            solver.add_fix(host, (target - host).length());
+        }
+        solver
+    }
+
+    #[test]
+    fn exact_fix() {
+        // on the same plane as the hosts, so both solutions are the same point
+        let target = Position::new(12.0, 64.0, -30.0);
+        let hosts = [
+            Position::new(0.0, 64.0, 0.0),
+            Position::new(20.0, 64.0, 0.0),
+            Position::new(0.0, 64.0, 20.0),
+        ];
+
+        assert_eq!(
+            solver(target, &hosts).solve(),
+            GpsSolution::Position(target)
+        );
+    }
+
+    #[test]
+    fn ambiguous_when_coplanar() {
+        let target = Position::new(5.0, 10.0, 3.0);
+        // every host is at y = 0, so the target could be mirrored below the plane
+        let hosts = [
+            Position::new(0.0, 0.0, 0.0),
+            Position::new(10.0, 0.0, 0.0),
+            Position::new(0.0, 0.0, 10.0),
+        ];
+
+        match solver(target, &hosts).solve() {
+            GpsSolution::Ambiguous(p1, p2) => {
+                let mirrored = Position::new(5.0, -10.0, 3.0);
+                assert!(
+                    (p1 == target && p2 == mirrored) || (p1 == mirrored && p2 == target),
+                    "{p1:?} {p2:?}"
+                );
+            }
+            solution => panic!("expected an ambiguous solution, got {solution:?}"),
+        }
+    }
+
+    #[test]
+    fn fourth_fix_narrows() {
+        let target = Position::new(5.0, 10.0, 3.0);
+        let hosts = [
+            Position::new(0.0, 0.0, 0.0),
+            Position::new(10.0, 0.0, 0.0),
+            Position::new(0.0, 0.0, 10.0),
+            Position::new(0.0, 20.0, 0.0),
+        ];
+
+        assert_eq!(
+            solver(target, &hosts).solve(),
+            GpsSolution::Position(target)
+        );
+    }
+
+    #[test]
+    fn collinear_is_degenerate() {
+        let target = Position::new(5.0, 10.0, 3.0);
+        let hosts = [
+            Position::new(0.0, 0.0, 0.0),
+            Position::new(10.0, 0.0, 0.0),
+            Position::new(20.0, 0.0, 0.0),
+        ];
+
+        assert_eq!(solver(target, &hosts).solve(), GpsSolution::Degenerate);
+    }
+
+    #[test]
+    fn not_enough_fixes() {
+        let target = Position::new(5.0, 10.0, 3.0);
+        let hosts = [Position::new(0.0, 0.0, 0.0), Position::new(10.0, 0.0, 0.0)];
+
+        assert_eq!(solver(target, &hosts).solve(), GpsSolution::NotEnoughFixes);
+    }
+}
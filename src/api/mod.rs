@@ -0,0 +1,76 @@
+use serde::de::DeserializeOwned;
+
+use crate::{
+    computer::{deserialize_optional, Computer},
+    error::Result,
+    peripheral::PeripheralCallResult,
+    request::PeripheralArgs,
+};
+
+pub mod commands;
+pub mod fs;
+pub mod gps;
+pub mod os;
+pub mod rednet;
+pub mod redstone;
+
+/// A global Lua API on a computer (i.e. `os`, `redstone`, `fs`).
+#[derive(Debug, Clone)]
+pub struct Api<'a> {
+    pub(crate) computer: &'a Computer,
+    pub(crate) name: String,
+}
+
+impl Computer {
+    /// Access an arbitrary global API by name.
+    pub fn api(&self, name: impl ToString) -> Api<'_> {
+        Api {
+            computer: self,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl<'a> Api<'a> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub async fn call_method<S: PeripheralArgs>(
+        &self,
+        method: impl Into<String>,
+        args: S,
+    ) -> PeripheralCallResult {
+        self.computer
+            .global_call_method(self.name.clone(), method.into(), args)
+            .await
+    }
+
+    pub async fn call_method_with<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        method: impl Into<String>,
+        args: S,
+    ) -> Result<T> {
+        self.computer
+            .global_call_into(self.name.clone(), method.into(), args)
+            .await
+    }
+
+    pub async fn call_method_with_optional<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        method: impl Into<String>,
+        args: S,
+    ) -> Result<Option<T>> {
+        deserialize_optional(self.call_method(method, args).await?)
+    }
+
+    pub async fn call_method_with_raw<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        method: impl Into<String>,
+        args: S,
+    ) -> Result<T> {
+        self.computer
+            .global_call_into_raw(self.name.clone(), method.into(), args)
+            .await
+    }
+}
@@ -0,0 +1,209 @@
+use std::{
+    future::{Future, IntoFuture},
+    pin::Pin,
+};
+
+use serde_json::Value;
+
+use crate::{
+    computer::Computer,
+    error::{as_error, Error, Result},
+    event::EventReceiver,
+};
+
+use super::Api;
+
+/// The clock used by [`Os::time`], [`Os::day`] and [`Os::epoch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    /// The in-game clock of the world the computer is in.
+    Ingame,
+    Utc,
+    /// The local time of the Minecraft server.
+    Local,
+}
+
+#[derive(Debug, Clone)]
+pub struct Os<'a> {
+    inner: Api<'a>,
+}
+
+impl Computer {
+    pub fn os(&self) -> Os<'_> {
+        Os {
+            inner: self.api("os"),
+        }
+    }
+}
+
+impl<'a> Os<'a> {
+    pub async fn get_computer_id(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getComputerID", Value::Null)
+            .await
+    }
+
+    pub async fn get_computer_label(&self) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getComputerLabel", Value::Null)
+            .await
+    }
+
+    pub async fn set_computer_label(&self, label: impl ToString) -> Result<()> {
+        self.inner
+            .call_method("setComputerLabel", label.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn clear_computer_label(&self) -> Result<()> {
+        self.inner
+            .call_method("setComputerLabel", Value::Null)
+            .await?;
+
+        Ok(())
+    }
+
+    /// The number of seconds the computer has been running for.
+    pub async fn clock(&self) -> Result<f64> {
+        self.inner.call_method_with("clock", Value::Null).await
+    }
+
+    /// The current time in hours, between 0 and 24.
+    pub async fn time(&self, locale: Locale) -> Result<f64> {
+        self.inner.call_method_with("time", locale).await
+    }
+
+    pub async fn day(&self, locale: Locale) -> Result<u64> {
+        self.inner.call_method_with("day", locale).await
+    }
+
+    /// The number of milliseconds since the epoch of the given clock.
+    pub async fn epoch(&self, locale: Locale) -> Result<u64> {
+        // this can be large enough for Lua to print it in exponent form
+        let epoch: f64 = self.inner.call_method_with("epoch", locale).await?;
+
+        Ok(epoch as u64)
+    }
+
+    /// Starts a timer that resolves after `seconds`, rounded up to the nearest tick.
+    pub async fn start_timer(&self, seconds: f64) -> Result<Timer<'a>> {
+        // subscribe before starting the timer so a short timer can't fire before we listen
+        let events = self.inner.computer.subscribe("timer").await?;
+        let id = self.inner.call_method_with("startTimer", seconds).await?;
+
+        Ok(Timer {
+            os: self.clone(),
+            id,
+            events,
+        })
+    }
+
+    pub async fn cancel_timer(&self, id: u64) -> Result<()> {
+        self.inner.call_method("cancelTimer", id).await?;
+
+        Ok(())
+    }
+
+    /// Sets an alarm that resolves at the in-game `time`, in hours between 0 and 24.
+    pub async fn set_alarm(&self, time: f64) -> Result<Alarm<'a>> {
+        let events = self.inner.computer.subscribe("alarm").await?;
+        let id = self.inner.call_method_with("setAlarm", time).await?;
+
+        Ok(Alarm {
+            os: self.clone(),
+            id,
+            events,
+        })
+    }
+
+    pub async fn cancel_alarm(&self, id: u64) -> Result<()> {
+        self.inner.call_method("cancelAlarm", id).await?;
+
+        Ok(())
+    }
+
+    /// Waits for `seconds` using the computer's clock.
+    pub async fn sleep(&self, seconds: f64) -> Result<()> {
+        self.start_timer(seconds).await?.await
+    }
+
+    /// Shuts down the computer, which will disconnect it.
+    pub async fn shutdown(&self) -> Result<()> {
+        self.call_and_disconnect("shutdown").await
+    }
+
+    /// Reboots the computer, which will disconnect it. If the worker is started on boot,
+    /// it will reconnect as a new [`Computer`].
+    pub async fn reboot(&self) -> Result<()> {
+        self.call_and_disconnect("reboot").await
+    }
+
+    async fn call_and_disconnect(&self, method: &str) -> Result<()> {
+        match self.inner.call_method(method, Value::Null).await {
+            Ok(_) => Ok(()),
+            Err(err) => match as_error(&err) {
+                Some(Error::Disconnected) => Ok(()),
+                _ => Err(err),
+            },
+        }
+    }
+}
+
+async fn wait_for_id(events: &mut EventReceiver, id: u64) -> Result<()> {
+    loop {
+        let event = events.recv().await?;
+        if event.param::<u64>(0)? == id {
+            return Ok(());
+        }
+    }
+}
+
+macro_rules! impl_pending_event {
+    ($ty:ident, $cancel_fn:ident) => {
+        impl<'a> $ty<'a> {
+            pub fn id(&self) -> u64 {
+                self.id
+            }
+
+            pub async fn wait(mut self) -> Result<()> {
+                wait_for_id(&mut self.events, self.id).await
+            }
+
+            pub async fn cancel(self) -> Result<()> {
+                self.os.$cancel_fn(self.id).await
+            }
+        }
+
+        impl<'a> IntoFuture for $ty<'a> {
+            type Output = Result<()>;
+            type IntoFuture = Pin<Box<dyn Future<Output = Self::Output> + Send + 'a>>;
+
+            fn into_future(self) -> Self::IntoFuture {
+                Box::pin(self.wait())
+            }
+        }
+    };
+}
+
+/// A timer started with [`Os::start_timer`]. Await it to wait for the timer to fire.
+#[derive(Debug)]
+pub struct Timer<'a> {
+    os: Os<'a>,
+    id: u64,
+    events: EventReceiver,
+}
+
+impl_pending_event!(Timer, cancel_timer);
+
+/// An alarm set with [`Os::set_alarm`]. Await it to wait for the alarm to go off.
+#[derive(Debug)]
+pub struct Alarm<'a> {
+    os: Os<'a>,
+    id: u64,
+    events: EventReceiver,
+}
+
+impl_pending_event!(Alarm, cancel_alarm);
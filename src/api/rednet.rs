@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use futures_util::Stream;
+use serde_json::Value;
+
+use crate::{
+    computer::Computer,
+    error::Result,
+    event::{Event, EventReceiver},
+};
+
+use super::Api;
+
+mod router;
+pub use router::*;
+
+/// A message received over rednet, from the `rednet_message` event.
+#[derive(Debug, Clone)]
+pub struct RednetMessage {
+    /// The ID of the computer that sent the message.
+    pub sender: u64,
+    pub message: Value,
+    pub protocol: Option<String>,
+}
+
+impl RednetMessage {
+    pub fn from_event(event: &Event) -> Result<Self> {
+        Ok(Self {
+            sender: event.param(0)?,
+            message: event.param(1)?,
+            protocol: event.param(2)?,
+        })
+    }
+}
+
+/// Receives rednet messages, optionally only those sent with a specific protocol.
+#[derive(Debug)]
+pub struct RednetReceiver {
+    protocol: Option<String>,
+    events: EventReceiver,
+}
+
+impl RednetReceiver {
+    pub async fn recv(&mut self) -> Result<RednetMessage> {
+        loop {
+            let event = self.events.recv().await?;
+            let message = RednetMessage::from_event(&event)?;
+            if self.protocol.is_none() || message.protocol == self.protocol {
+                return Ok(message);
+            }
+        }
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = RednetMessage> + Send + Unpin {
+        Box::pin(futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.ok().map(|message| (message, rx))
+        }))
+    }
+}
+
+/// The `rednet` API. Messages are sent and received through the modems opened with
+/// [`Rednet::open`], and are received by computers that are not running the worker.
+#[derive(Debug, Clone)]
+pub struct Rednet<'a> {
+    inner: Api<'a>,
+}
+
+impl Computer {
+    pub fn rednet(&self) -> Rednet<'_> {
+        Rednet {
+            inner: self.api("rednet"),
+        }
+    }
+}
+
+impl<'a> Rednet<'a> {
+    /// Opens the modem with the given address (i.e. [`Modem::address`]) for rednet.
+    ///
+    /// [`Modem::address`]: crate::wrappers::modem::Modem::address
+    pub async fn open(&self, modem: impl ToString) -> Result<()> {
+        self.inner.call_method("open", modem.to_string()).await?;
+
+        Ok(())
+    }
+
+    pub async fn close(&self, modem: impl ToString) -> Result<()> {
+        self.inner.call_method("close", modem.to_string()).await?;
+
+        Ok(())
+    }
+
+    pub async fn close_all(&self) -> Result<()> {
+        self.inner.call_method("close", Value::Null).await?;
+
+        Ok(())
+    }
+
+    pub async fn is_open(&self, modem: impl ToString) -> Result<bool> {
+        self.inner
+            .call_method_with("isOpen", modem.to_string())
+            .await
+    }
+
+    /// Returns true if any modem is open for rednet.
+    pub async fn is_any_open(&self) -> Result<bool> {
+        self.inner.call_method_with("isOpen", Value::Null).await
+    }
+
+    /// Sends a message to the computer with the ID `recipient`, returning whether it was sent.
+    pub async fn send(
+        &self,
+        recipient: u64,
+        message: impl serde::Serialize,
+        protocol: Option<&str>,
+    ) -> Result<bool> {
+        let message = serde_json::to_value(message)?;
+        self.inner
+            .call_method_with("send", (recipient, message, protocol.map(str::to_string)))
+            .await
+    }
+
+    pub async fn broadcast(
+        &self,
+        message: impl serde::Serialize,
+        protocol: Option<&str>,
+    ) -> Result<()> {
+        let message = serde_json::to_value(message)?;
+        self.inner
+            .call_method("broadcast", (message, protocol.map(str::to_string)))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Subscribes to every rednet message received from now on, optionally filtered by protocol.
+    pub async fn messages(&self, protocol: Option<&str>) -> Result<RednetReceiver> {
+        Ok(RednetReceiver {
+            protocol: protocol.map(str::to_string),
+            events: self.inner.computer.subscribe("rednet_message").await?,
+        })
+    }
+
+    /// Waits for the next rednet message, returning `None` if `timeout` elapses first.
+    pub async fn receive(
+        &self,
+        protocol: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<Option<RednetMessage>> {
+        let mut messages = self.messages(protocol).await?;
+
+        match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, messages.recv()).await {
+                Ok(message) => message.map(Some),
+                Err(_) => Ok(None),
+            },
+            None => messages.recv().await.map(Some),
+        }
+    }
+
+    /// Registers this computer as `hostname` under `protocol`, so it can be found with [`Rednet::lookup`].
+    pub async fn host(&self, protocol: impl ToString, hostname: impl ToString) -> Result<()> {
+        self.inner
+            .call_method("host", (protocol.to_string(), hostname.to_string()))
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn unhost(&self, protocol: impl ToString) -> Result<()> {
+        self.inner
+            .call_method("unhost", protocol.to_string())
+            .await?;
+
+        Ok(())
+    }
+
+    /// Finds the IDs of every computer hosting `protocol`. This takes a couple of seconds.
+    pub async fn lookup(&self, protocol: impl ToString) -> Result<Vec<u64>> {
+        self.inner
+            .call_method_with_raw("lookup", protocol.to_string())
+            .await
+    }
+
+    /// Finds the ID of the computer hosting `protocol` as `hostname`.
+    pub async fn lookup_host(
+        &self,
+        protocol: impl ToString,
+        hostname: impl ToString,
+    ) -> Result<Option<u64>> {
+        self.inner
+            .call_method_with_optional("lookup", (protocol.to_string(), hostname.to_string()))
+            .await
+    }
+}
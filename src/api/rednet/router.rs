@@ -0,0 +1,417 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use crate::{
+    computer::{Computer, WeakComputer},
+    error::{as_error, Error, Result},
+    lua_compat::LuaVec,
+    Server,
+};
+
+use super::{RednetMessage, RednetReceiver};
+
+/// Decides which rednet messages a [`RednetRouter`] relays, and where to.
+///
+/// A new rule matches every message and broadcasts it from every other computer.
+#[derive(Debug, Clone, Default)]
+pub struct RouteRule {
+    protocol: Option<String>,
+    sender: Option<u64>,
+    from: Option<String>,
+    to: Option<Vec<String>>,
+    recipient: Option<u64>,
+}
+
+impl RouteRule {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only match messages sent with `protocol`.
+    pub fn protocol(self, protocol: impl ToString) -> Self {
+        Self {
+            protocol: Some(protocol.to_string()),
+            ..self
+        }
+    }
+
+    /// Only match messages sent by the computer with the ID `sender`.
+    pub fn sender(self, sender: u64) -> Self {
+        Self {
+            sender: Some(sender),
+            ..self
+        }
+    }
+
+    /// Only match messages received by the worker named `name`.
+    pub fn from_worker(self, name: impl ToString) -> Self {
+        Self {
+            from: Some(name.to_string()),
+            ..self
+        }
+    }
+
+    /// Only relay messages through the workers with these names.
+    pub fn to_workers<S: ToString>(self, names: impl IntoIterator<Item = S>) -> Self {
+        Self {
+            to: Some(names.into_iter().map(|name| name.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Send relayed messages to the computer with the ID `recipient` instead of broadcasting them.
+    pub fn to_recipient(self, recipient: u64) -> Self {
+        Self {
+            recipient: Some(recipient),
+            ..self
+        }
+    }
+
+    fn matches(&self, source: &Attached, message: &RednetMessage) -> bool {
+        (self.protocol.is_none() || self.protocol == message.protocol)
+            && (self.sender.is_none() || self.sender == Some(message.sender))
+            && (self.from.is_none() || self.from == source.name)
+    }
+
+    fn targets(&self, target: &Attached) -> bool {
+        match (&self.to, &target.name) {
+            (None, _) => true,
+            (Some(names), Some(name)) => names.contains(name),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Relays rednet messages between the computers connected to a [`Server`], so a single
+/// rednet network can span several worlds or servers.
+///
+/// Every message received by a connected computer is checked against the rules in order,
+/// and the first matching rule decides where it is relayed. Relayed messages are sent by
+/// the relaying worker, so receivers will see the worker's ID as the sender.
+///
+/// To prevent loops, a message that the router relayed through a worker is not relayed again
+/// when another worker in range of it hears it. To find out which workers are in range of each
+/// other, every worker broadcasts a probe (with the protocol [`PROBE_PROTOCOL`]) whenever a
+/// computer is attached, so programs receiving rednet messages without a protocol may see these.
+///
+/// Identical messages from the same sender are only relayed once within the
+/// [`RednetRouter::dedup_window`], so a program sending the same message twice in quick
+/// succession (e.g. a repeated `"open"` command) only has the first one relayed.
+#[derive(Debug, Clone)]
+pub struct RednetRouter {
+    rules: Vec<RouteRule>,
+    open_modems: bool,
+    dedup_window: Duration,
+}
+
+impl Default for RednetRouter {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            open_modems: true,
+            dedup_window: Duration::from_secs(1),
+        }
+    }
+}
+
+/// The protocol the probes a [`RednetRouter`] uses to find workers in range of each other are sent with.
+pub const PROBE_PROTOCOL: &str = "computercraft-rs:router_probe";
+
+// how long to remember messages we relayed, long enough that a slow echo can't start a loop
+const RELAYED_WINDOW: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+struct RouterState {
+    attached: HashMap<usize, Attached>,
+    // (when, fingerprint) of recently routed messages, to only relay each one once
+    recent: VecDeque<(Instant, String)>,
+    // (when, target key, payload) of every relay, to recognise it when another worker hears it
+    relayed: VecDeque<(Instant, usize, String)>,
+    // the keys of the workers each worker has heard a probe from, i.e. those in range of it
+    hears: HashMap<usize, HashSet<usize>>,
+    // tells our probes apart from those of other routers
+    probe_id: String,
+}
+
+#[derive(Debug)]
+struct Attached {
+    computer: Computer,
+    name: Option<String>,
+    id: u64,
+}
+
+#[derive(Debug)]
+enum RouterMessage {
+    Attached(usize, Attached),
+    Received(usize, RednetMessage),
+    Detached(usize),
+}
+
+impl RednetRouter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: RouteRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Whether every modem on a computer should be opened for rednet when it is attached.
+    /// This is enabled by default.
+    pub fn open_modems(self, open_modems: bool) -> Self {
+        Self {
+            open_modems,
+            ..self
+        }
+    }
+
+    /// Identical messages received by several computers within this window
+    /// (i.e. two workers in range of the same sender) are only relayed once. This also means
+    /// the same message sent twice within the window is only relayed once. Defaults to a second.
+    pub fn dedup_window(self, dedup_window: Duration) -> Self {
+        Self {
+            dedup_window,
+            ..self
+        }
+    }
+
+    /// Runs the router until the server stops. Computers that connect after this is called
+    /// are attached automatically, and computers that disconnect are detached.
+    pub async fn run(self, server: &Server) -> Result<()> {
+        let mut connections = server.connections();
+        let (tx, mut rx) = unbounded_channel();
+        // every computer that is attached or being attached, so that one that connects between
+        // subscribing to connections and listing the computers isn't attached twice
+        let mut known = HashMap::new();
+        let mut state = RouterState {
+            attached: HashMap::new(),
+            recent: VecDeque::new(),
+            relayed: VecDeque::new(),
+            hears: HashMap::new(),
+            probe_id: uuid::Uuid::new_v4().to_string(),
+        };
+        let mut next_key = 0;
+
+        for computer in server.computers() {
+            self.attach(computer, &mut next_key, &mut known, &tx);
+        }
+
+        loop {
+            tokio::select! {
+                computer = connections.recv() => match computer {
+                    Ok(computer) => self.attach(computer, &mut next_key, &mut known, &tx),
+                    Err(err) if matches!(as_error(&err), Some(Error::ConnectionsLagged(_))) => {
+                        // the computers that were missed are still in the list, and the
+                        // ones already attached are skipped
+                        for computer in server.computers() {
+                            self.attach(computer, &mut next_key, &mut known, &tx);
+                        }
+                    }
+                    Err(err) => return Err(err),
+                },
+                Some(message) = rx.recv() => match message {
+                    RouterMessage::Attached(key, computer) => {
+                        debug!("Attached computer {} to rednet router", computer.id);
+                        state.attached.insert(key, computer);
+                        // every worker probes again, so both the new one and the
+                        // ones already attached find out whether they can hear each other
+                        Self::probe(&state);
+                    }
+                    RouterMessage::Received(key, message) => {
+                        self.route(&mut state, key, message);
+                    }
+                    RouterMessage::Detached(key) => {
+                        known.remove(&key);
+                        state.hears.remove(&key);
+                        for heard in state.hears.values_mut() {
+                            heard.remove(&key);
+                        }
+                        if let Some(computer) = state.attached.remove(&key) {
+                            debug!("Detached computer {} from rednet router", computer.id);
+                        }
+                    }
+                },
+            }
+        }
+    }
+
+    fn attach(
+        &self,
+        computer: Computer,
+        next_key: &mut usize,
+        known: &mut HashMap<usize, WeakComputer>,
+        tx: &UnboundedSender<RouterMessage>,
+    ) {
+        if known.values().any(|known| known.is(&computer)) {
+            return;
+        }
+
+        let key = *next_key;
+        *next_key += 1;
+        known.insert(key, computer.downgrade());
+
+        // attaching makes several calls to the computer, so it's done in the
+        // background where a slow computer can't hold up routing for the others
+        let open_modems = self.open_modems;
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let mut messages = match Self::try_attach(computer, open_modems).await {
+                Ok((computer, messages)) => {
+                    if tx.send(RouterMessage::Attached(key, computer)).is_err() {
+                        return;
+                    }
+                    messages
+                }
+                Err(err) => {
+                    warn!("Failed to attach computer to rednet router: {}", err);
+                    let _ = tx.send(RouterMessage::Detached(key));
+                    return;
+                }
+            };
+
+            while let Ok(message) = messages.recv().await {
+                if tx.send(RouterMessage::Received(key, message)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(RouterMessage::Detached(key));
+        });
+    }
+
+    async fn try_attach(
+        computer: Computer,
+        open_modems: bool,
+    ) -> Result<(Attached, RednetReceiver)> {
+        let name = computer.computer_info()?.name.clone();
+        let id = computer.os().get_computer_id().await?;
+
+        if open_modems {
+            let peripherals = computer.api("peripheral");
+            let names: LuaVec<String> = peripherals
+                .call_method_with("getNames", Value::Null)
+                .await?;
+            for address in names.iter() {
+                let is_modem: bool = peripherals
+                    .call_method_with("hasType", (address.clone(), "modem"))
+                    .await?;
+                if is_modem {
+                    computer.rednet().open(address).await?;
+                }
+            }
+        }
+
+        let messages = computer.rednet().messages(None).await?;
+
+        Ok((Attached { computer, name, id }, messages))
+    }
+
+    fn probe(state: &RouterState) {
+        for (key, worker) in &state.attached {
+            let computer = worker.computer.clone();
+            let probe = json!({ "router": state.probe_id, "key": key });
+            tokio::spawn(async move {
+                if let Err(err) = computer
+                    .rednet()
+                    .broadcast(probe, Some(PROBE_PROTOCOL))
+                    .await
+                {
+                    warn!("Failed to send rednet router probe: {}", err);
+                }
+            });
+        }
+    }
+
+    fn route(&self, state: &mut RouterState, key: usize, message: RednetMessage) {
+        let RouterState {
+            attached,
+            recent,
+            relayed,
+            hears,
+            probe_id,
+        } = state;
+        let Some(source) = attached.get(&key) else {
+            return;
+        };
+
+        if message.protocol.as_deref() == Some(PROBE_PROTOCOL) {
+            if message.message["router"] == **probe_id {
+                if let Some(heard) = message.message["key"].as_u64() {
+                    hears.entry(key).or_default().insert(heard as usize);
+                }
+            }
+            return;
+        }
+
+        let now = Instant::now();
+        while matches!(relayed.front(), Some((at, ..)) if now.duration_since(*at) > RELAYED_WINDOW)
+        {
+            relayed.pop_front();
+        }
+        // IDs are only unique within a world, so a message is only taken for one we relayed
+        // if it came from the ID of a worker we relayed it through, and that worker is in range
+        let payload = format!("{:?}:{}", message.protocol, message.message);
+        let in_range = hears.get(&key);
+        let is_echo = relayed.iter().any(|(_, target, seen)| {
+            *seen == payload
+                && in_range.is_some_and(|in_range| in_range.contains(target))
+                && attached
+                    .get(target)
+                    .is_some_and(|target| target.id == message.sender)
+        });
+        if is_echo {
+            return;
+        }
+
+        let Some(rule) = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(source, &message))
+        else {
+            return;
+        };
+
+        while matches!(recent.front(), Some((at, _)) if now.duration_since(*at) > self.dedup_window)
+        {
+            recent.pop_front();
+        }
+        let fingerprint = format!(
+            "{}:{:?}:{}",
+            message.sender, message.protocol, message.message
+        );
+        if recent.iter().any(|(_, seen)| *seen == fingerprint) {
+            return;
+        }
+        recent.push_back((now, fingerprint));
+
+        for (target_key, target) in attached {
+            if *target_key == key || !rule.targets(target) {
+                continue;
+            }
+            relayed.push_back((now, *target_key, payload.clone()));
+
+            let computer = target.computer.clone();
+            let recipient = rule.recipient;
+            let message = message.clone();
+            tokio::spawn(async move {
+                let rednet = computer.rednet();
+                let protocol = message.protocol.as_deref();
+                let res = match recipient {
+                    Some(recipient) => rednet
+                        .send(recipient, message.message.clone(), protocol)
+                        .await
+                        .map(|_| ()),
+                    None => rednet.broadcast(message.message.clone(), protocol).await,
+                };
+                if let Err(err) = res {
+                    warn!("Failed to relay rednet message: {}", err);
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use serde_json::Value;
+
+use crate::{computer::Computer, error::Result, event::EventReceiver};
+
+#[cfg(feature = "peripheral-wrappers")]
+use crate::wrappers::shared::color::ColorSet;
+
+use super::Api;
+
+mod side;
+pub use side::*;
+mod redstone_level;
+pub use redstone_level::*;
+
+#[derive(Debug, Clone)]
+pub struct Redstone<'a> {
+    inner: Api<'a>,
+}
+
+impl Computer {
+    pub fn redstone(&self) -> Redstone<'_> {
+        Redstone {
+            inner: self.api("redstone"),
+        }
+    }
+}
+
+impl<'a> Redstone<'a> {
+    pub async fn get_sides(&self) -> Result<Vec<Side>> {
+        self.inner.call_method_with("getSides", Value::Null).await
+    }
+
+    pub async fn get_input(&self, side: Side) -> Result<bool> {
+        self.inner.call_method_with("getInput", side).await
+    }
+
+    pub async fn get_output(&self, side: Side) -> Result<bool> {
+        self.inner.call_method_with("getOutput", side).await
+    }
+
+    pub async fn set_output(&self, side: Side, on: bool) -> Result<()> {
+        self.inner.call_method("setOutput", (side, on)).await?;
+
+        Ok(())
+    }
+
+    pub async fn get_analog_input(&self, side: Side) -> Result<RedstoneLevel> {
+        self.inner.call_method_with("getAnalogInput", side).await
+    }
+
+    pub async fn get_analog_output(&self, side: Side) -> Result<RedstoneLevel> {
+        self.inner.call_method_with("getAnalogOutput", side).await
+    }
+
+    pub async fn set_analog_output(&self, side: Side, level: RedstoneLevel) -> Result<()> {
+        self.inner
+            .call_method("setAnalogOutput", (side, level))
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(feature = "peripheral-wrappers")]
+    pub async fn get_bundled_input(&self, side: Side) -> Result<ColorSet> {
+        self.inner.call_method_with("getBundledInput", side).await
+    }
+
+    #[cfg(feature = "peripheral-wrappers")]
+    pub async fn get_bundled_output(&self, side: Side) -> Result<ColorSet> {
+        self.inner.call_method_with("getBundledOutput", side).await
+    }
+
+    #[cfg(feature = "peripheral-wrappers")]
+    pub async fn set_bundled_output(&self, side: Side, colors: ColorSet) -> Result<()> {
+        self.inner
+            .call_method("setBundledOutput", (side, colors))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns true if every color in `colors` is on in the bundled input.
+    #[cfg(feature = "peripheral-wrappers")]
+    pub async fn test_bundled_input(&self, side: Side, colors: ColorSet) -> Result<bool> {
+        self.inner
+            .call_method_with("testBundledInput", (side, colors))
+            .await
+    }
+
+    /// Subscribes to the `redstone` event, which is queued whenever any redstone input changes.
+    pub async fn events(&self) -> Result<EventReceiver> {
+        self.inner.computer.subscribe("redstone").await
+    }
+}
@@ -0,0 +1,46 @@
+use serde_json::Value;
+use thiserror::Error;
+
+/// An analog redstone signal strength, between 0 and 15 inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8")]
+pub struct RedstoneLevel(pub(super) u8);
+
+impl RedstoneLevel {
+    pub const MIN: RedstoneLevel = RedstoneLevel(0);
+    pub const MAX: RedstoneLevel = RedstoneLevel(15);
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TryParseRedstoneLevelError {
+    #[error("Redstone level out of range. Must be between 0 and 15")]
+    OutOfRange,
+}
+
+impl TryFrom<u8> for RedstoneLevel {
+    type Error = TryParseRedstoneLevelError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > 15 {
+            return Err(TryParseRedstoneLevelError::OutOfRange);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<RedstoneLevel> for u8 {
+    fn from(value: RedstoneLevel) -> Self {
+        value.0
+    }
+}
+
+impl From<RedstoneLevel> for Value {
+    fn from(value: RedstoneLevel) -> Self {
+        value.0.into()
+    }
+}
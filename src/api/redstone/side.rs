@@ -0,0 +1,40 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Front,
+    Back,
+}
+
+impl Side {
+    pub fn sides() -> [Side; 6] {
+        [
+            Side::Top,
+            Side::Bottom,
+            Side::Left,
+            Side::Right,
+            Side::Front,
+            Side::Back,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Side::Top => "top",
+            Side::Bottom => "bottom",
+            Side::Left => "left",
+            Side::Right => "right",
+            Side::Front => "front",
+            Side::Back => "back",
+        }
+    }
+}
+
+impl std::fmt::Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
     collections::HashMap,
-    sync::{Arc, OnceLock},
+    sync::{Arc, OnceLock, Weak},
 };
 
 use futures_util::{SinkExt, StreamExt};
@@ -10,7 +10,11 @@ use thiserror::Error;
 use tokio::{
     net::TcpStream,
     select,
-    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    sync::{
+        broadcast,
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     task::JoinHandle,
 };
 use tokio_tungstenite::{tungstenite::Error as WsError, WebSocketStream};
@@ -19,12 +23,14 @@ use uuid::Uuid;
 use crate::{
     debug_feature,
     error::{Error, Result},
+    event::{Event, EventReceiver},
+    lua_compat::LuaVec,
     peripheral::{Peripheral, PeripheralCallResult},
     request::{CCRequest, CCRequestKind, PeripheralArgs},
     response::{CCResponse, CCResponseKind, ParseResponseError},
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Computer {
     inner: Arc<ComputerInner>,
     computer_info: OnceLock<ComputerInfo>,
@@ -53,9 +59,10 @@ macro_rules! impl_requests {
 impl Computer {
     pub(crate) async fn new(ws: WebSocketStream<TcpStream>) -> Result<Self> {
         let (tx, rx) = unbounded_channel();
-        let handle = tokio::spawn(computer_thread(ws, rx));
+        let (events_tx, events) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let handle = tokio::spawn(computer_thread(ws, rx, events_tx));
         let mut inst = Self {
-            inner: Arc::new(ComputerInner { handle, tx }),
+            inner: Arc::new(ComputerInner { handle, tx, events }),
             computer_info: OnceLock::new(),
         };
 
@@ -77,6 +84,19 @@ impl Computer {
         }
     }
 
+    /// A handle that doesn't keep the connection open, see [`WeakComputer`].
+    pub(crate) fn downgrade(&self) -> WeakComputer {
+        WeakComputer {
+            inner: Arc::downgrade(&self.inner),
+            computer_info: self.computer_info.clone(),
+        }
+    }
+
+    /// Returns false once the worker has disconnected.
+    pub fn is_connected(&self) -> bool {
+        !self.inner.handle.is_finished()
+    }
+
     pub fn computer_info(&self) -> Result<&ComputerInfo> {
         debug_feature!(self.computer_info.get().ok_or(Error::HandshakeFailed))
     }
@@ -88,6 +108,7 @@ impl Computer {
             Ok(Peripheral {
                 computer: self,
                 address,
+                types: OnceLock::new(),
             })
         } else {
             debug_feature!(Err(Error::PeripheralNotFound(address)))
@@ -122,13 +143,7 @@ impl Computer {
                 success,
                 error,
                 result,
-            } => {
-                if success {
-                    Ok(result.unwrap_or_default())
-                } else {
-                    debug_feature!(Err(Error::LuaError(error.unwrap_or_default())))
-                }
-            }
+            } => call_result(success, error, result),
             _ => debug_feature!(Err(Error::WrongResponseType(res))),
         }
     }
@@ -139,14 +154,7 @@ impl Computer {
         method: String,
         args: S,
     ) -> Result<T> {
-        match &self.peripheral_call_method(address, method, args).await?[..] {
-            #[cfg(not(feature = "debug"))]
-            [val] => Ok(T::deserialize(val)?),
-            #[cfg(feature = "debug")]
-            [val] => Ok(serde_path_to_error::deserialize(val)?),
-            [] => debug_feature!(Err(Error::NoReturnValues)),
-            _ => debug_feature!(Err(Error::MultipleReturnValues)),
-        }
+        deserialize_single(self.peripheral_call_method(address, method, args).await?)
     }
 
     pub(crate) async fn peripheral_call_into_raw<S: PeripheralArgs, T: DeserializeOwned>(
@@ -155,22 +163,129 @@ impl Computer {
         method: String,
         args: S,
     ) -> Result<T> {
-        let val = Value::Array(self.peripheral_call_method(address, method, args).await?);
+        deserialize_all(self.peripheral_call_method(address, method, args).await?)
+    }
 
-        #[cfg(not(feature = "debug"))]
-        return Ok(T::deserialize(val)?);
+    pub(crate) async fn global_call_method<S: PeripheralArgs>(
+        &self,
+        api: String,
+        method: String,
+        args: S,
+    ) -> PeripheralCallResult {
+        let res = self
+            .send_raw(CCRequestKind::CallGlobal {
+                api,
+                method,
+                args: Box::new(args),
+            })
+            .await?;
+        match res.response {
+            CCResponseKind::Disconnected => debug_feature!(Err(Error::Disconnected)),
+            CCResponseKind::CallGlobal {
+                success,
+                error,
+                result,
+            } => call_result(success, error, result),
+            _ => debug_feature!(Err(Error::WrongResponseType(res))),
+        }
+    }
+
+    pub(crate) async fn global_call_into<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        api: String,
+        method: String,
+        args: S,
+    ) -> Result<T> {
+        deserialize_single(self.global_call_method(api, method, args).await?)
+    }
+
+    pub(crate) async fn global_call_into_raw<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        api: String,
+        method: String,
+        args: S,
+    ) -> Result<T> {
+        deserialize_all(self.global_call_method(api, method, args).await?)
+    }
+
+    /// Subscribes to events named `event` that are queued on this computer.
+    pub async fn subscribe(&self, event: impl ToString) -> Result<EventReceiver> {
+        self.subscribe_all([event]).await
+    }
+
+    /// Subscribes to every event named in `events` with a single receiver.
+    pub async fn subscribe_all<S: ToString>(
+        &self,
+        events: impl IntoIterator<Item = S>,
+    ) -> Result<EventReceiver> {
+        let names = events
+            .into_iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+
+        // subscribe on our end first so we can't miss any events
+        // that arrive between the worker subscribing and us listening
+        let rx = self.inner.events.resubscribe();
+
+        for name in &names {
+            self.subscribe_event(name.clone()).await?;
+        }
+
+        Ok(EventReceiver::new(names, rx))
+    }
+}
 
+fn call_result(
+    success: bool,
+    error: Option<Vec<Value>>,
+    result: Option<Vec<Value>>,
+) -> PeripheralCallResult {
+    if success {
+        Ok(result.unwrap_or_default())
+    } else {
+        debug_feature!(Err(Error::LuaError(error.unwrap_or_default())))
+    }
+}
+
+pub(crate) fn deserialize_single<T: DeserializeOwned>(values: Vec<Value>) -> Result<T> {
+    match &values[..] {
+        #[cfg(not(feature = "debug"))]
+        [val] => Ok(T::deserialize(val)?),
         #[cfg(feature = "debug")]
-        return Ok(serde_path_to_error::deserialize(val)?);
+        [val] => Ok(serde_path_to_error::deserialize(val)?),
+        [] => debug_feature!(Err(Error::NoReturnValues)),
+        _ => debug_feature!(Err(Error::MultipleReturnValues)),
     }
 }
 
+/// Like [`deserialize_single`], but treats a `nil` return as `None`, including the
+/// `nil, "reason"` style of return.
+pub(crate) fn deserialize_optional<T: DeserializeOwned>(values: Vec<Value>) -> Result<Option<T>> {
+    match &values[..] {
+        [] | [Value::Null, ..] => Ok(None),
+        _ => deserialize_single(values).map(Some),
+    }
+}
+
+pub(crate) fn deserialize_all<T: DeserializeOwned>(values: Vec<Value>) -> Result<T> {
+    let val = Value::Array(values);
+
+    #[cfg(not(feature = "debug"))]
+    return Ok(T::deserialize(val)?);
+
+    #[cfg(feature = "debug")]
+    return Ok(serde_path_to_error::deserialize(val)?);
+}
+
 impl_requests! {
     Echo = pub echo => |msg: String| -> String;
     ConnectPeripheral = connect_peripheral => |address: String| -> bool;
-    GetPeripheralType = pub(crate) get_peripheral_type => |address: String| -> String;
+    GetPeripheralType = pub(crate) get_peripheral_types => |address: String| -> LuaVec<String>;
+    SubscribeEvent = subscribe_event => |event: String| -> bool;
 }
 
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
 #[derive(Debug, Clone, Copy, Deserialize)]
 pub enum ComputerKind {
     Computer,
@@ -182,15 +297,46 @@ pub enum ComputerKind {
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct ComputerInfo {
+    /// The computer's ID, `None` for workers that don't report it.
+    #[serde(default)]
+    pub id: Option<u64>,
     pub name: Option<String>,
     pub kind: ComputerKind,
     pub advanced: bool,
 }
 
+/// A [`Computer`] that doesn't keep the connection alive, the worker is still
+/// disconnected once every [`Computer`] is dropped.
+#[derive(Debug, Clone)]
+pub(crate) struct WeakComputer {
+    inner: Weak<ComputerInner>,
+    computer_info: OnceLock<ComputerInfo>,
+}
+
+impl WeakComputer {
+    /// Gets the computer back, unless it was dropped or has disconnected.
+    pub(crate) fn upgrade(&self) -> Option<Computer> {
+        let computer = Computer {
+            inner: self.inner.upgrade()?,
+            computer_info: self.computer_info.clone(),
+        };
+
+        computer.is_connected().then_some(computer)
+    }
+
+    /// Whether this is a handle to the same connection as `computer`.
+    pub(crate) fn is(&self, computer: &Computer) -> bool {
+        std::ptr::eq(self.inner.as_ptr(), Arc::as_ptr(&computer.inner))
+    }
+}
+
 #[derive(Debug)]
 struct ComputerInner {
     handle: JoinHandle<()>,
     tx: UnboundedSender<CCRequest>,
+    // only used to create new receivers, the sender lives in the computer
+    // thread so receivers are notified when the computer disconnects
+    events: broadcast::Receiver<Event>,
 }
 
 impl Drop for ComputerInner {
@@ -213,8 +359,12 @@ pub enum ComputerError {
     DispatchResponse(Uuid),
 }
 
-pub async fn computer_thread(ws: WebSocketStream<TcpStream>, rx: UnboundedReceiver<CCRequest>) {
-    if let Err(err) = computer_thread_inner(ws, rx).await {
+pub async fn computer_thread(
+    ws: WebSocketStream<TcpStream>,
+    rx: UnboundedReceiver<CCRequest>,
+    events: broadcast::Sender<Event>,
+) {
+    if let Err(err) = computer_thread_inner(ws, rx, events).await {
         error!("Computer thread failed: {}", err);
     }
 }
@@ -222,9 +372,35 @@ pub async fn computer_thread(ws: WebSocketStream<TcpStream>, rx: UnboundedReceiv
 async fn computer_thread_inner(
     mut ws: WebSocketStream<TcpStream>,
     mut rx: UnboundedReceiver<CCRequest>,
+    events: broadcast::Sender<Event>,
 ) -> Result<(), ComputerError> {
     let mut resolvers = HashMap::new();
+    let result = handle_messages(&mut ws, &mut rx, &events, &mut resolvers).await;
+
+    // however the socket went away (closed, reset or broken), anything still waiting
+    // for a response should see the computer as disconnected
+    let disconnected = CCResponse {
+        id: Uuid::nil(),
+        response: CCResponseKind::Disconnected,
+    };
+    rx.close();
+    while let Ok(request) = rx.try_recv() {
+        resolvers.insert(request.inner.id, request.resolver);
+    }
+    for (_, resolver) in resolvers {
+        // the caller may have stopped waiting, that's fine
+        let _ = resolver.send(disconnected.clone());
+    }
 
+    result
+}
+
+async fn handle_messages(
+    ws: &mut WebSocketStream<TcpStream>,
+    rx: &mut UnboundedReceiver<CCRequest>,
+    events: &broadcast::Sender<Event>,
+    resolvers: &mut HashMap<Uuid, oneshot::Sender<CCResponse>>,
+) -> Result<(), ComputerError> {
     loop {
         select! {
             Some(request) = rx.recv() => {
@@ -232,14 +408,22 @@ async fn computer_thread_inner(
                 resolvers.insert(request.inner.id, request.resolver);
                 ws.send(request.inner.as_message()).await.map_err(ComputerError::SendMessage)?;
             }
-            Some(msg) = ws.next() => {
+            msg = ws.next() => {
+                let Some(msg) = msg else {
+                    // the socket was closed, dropping the event sender lets
+                    // any event receivers know the computer is gone
+                    break Ok(());
+                };
                 let msg = msg.map_err(ComputerError::ReceiveMessage)?;
                 trace!("Received message: {:?}", msg);
                 let response = CCResponse::from_message(msg)?;
-                if let Some(resolver) = resolvers.remove(&response.id) {
+                if let CCResponseKind::Event(event) = response.response {
+                    // nobody listening is not an error, the event is just dropped
+                    let _ = events.send(event);
+                } else if let Some(resolver) = resolvers.remove(&response.id) {
                     resolver.send(response).map_err(|res| ComputerError::DispatchResponse(res.id))?;
                 } else if response.id == Uuid::nil() { // nil Uuid means the socket was closed
-                    for (_, resolver) in std::mem::take(&mut resolvers).into_iter() {
+                    for (_, resolver) in std::mem::take(resolvers).into_iter() {
                         resolver.send(response.clone()).map_err(|res| ComputerError::DispatchResponse(res.id))?;
                     }
                 } else {
@@ -21,6 +21,12 @@ pub enum Error {
     HandShookTwice,
     #[error("Handshake was not performed correctly and left the computer in an invalid state")]
     HandshakeFailed,
+    #[error("Connection receiver lagged behind, skipped {0} computers")]
+    ConnectionsLagged(u64),
+    #[error("Timed out waiting for computer {0} to connect")]
+    ConnectionTimeout(u64),
+    #[error("Computer is not a command computer")]
+    NotACommandComputer,
     #[error("Peripheral {0:?} was not found")]
     PeripheralNotFound(String),
     #[error("Peripheral is of type {0:?}, expected {1:?}")]
@@ -32,15 +38,34 @@ pub enum Error {
     #[error("Request was resolved with a response of the wrong type: {0:?}")]
     WrongResponseType(CCResponse),
     #[error("Error interacting with websocket: {0}")]
-    WsError(#[from] tokio_tungstenite::tungstenite::Error),
+    WsError(Box<tokio_tungstenite::tungstenite::Error>),
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
     #[error("Failed to deserialize returned data: {0}")]
     SerdeError(#[from] serde_json::Error),
 }
 
+// boxed because it's by far the largest variant
+impl From<tokio_tungstenite::tungstenite::Error> for Error {
+    fn from(err: tokio_tungstenite::tungstenite::Error) -> Self {
+        Self::WsError(Box::new(err))
+    }
+}
+
 #[cfg(not(feature = "debug"))]
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 #[cfg(feature = "debug")]
 pub type Result<T, E = eyre::Report> = std::result::Result<T, E>;
+
+/// Gets the [`Error`] behind a [`Result`]'s error, regardless of the `debug` feature.
+#[cfg(not(feature = "debug"))]
+pub(crate) fn as_error(err: &Error) -> Option<&Error> {
+    Some(err)
+}
+
+/// Gets the [`Error`] behind a [`Result`]'s error, regardless of the `debug` feature.
+#[cfg(feature = "debug")]
+pub(crate) fn as_error(err: &eyre::Report) -> Option<&Error> {
+    err.downcast_ref()
+}
@@ -0,0 +1,78 @@
+use futures_util::Stream;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::{
+    debug_feature,
+    error::{Error, Result},
+};
+
+/// An event that was queued on a Worker and forwarded to the Host.
+///
+/// Workers only forward events that have been subscribed to, see [`Computer::subscribe`].
+///
+/// [`Computer::subscribe`]: crate::computer::Computer::subscribe
+#[derive(Debug, Clone, Deserialize)]
+pub struct Event {
+    pub name: String,
+    #[serde(default)]
+    pub params: Vec<Value>,
+}
+
+impl Event {
+    /// Deserializes the parameter at `index` (not including the event name).
+    ///
+    /// Lua drops trailing `nil`s, so a missing parameter is treated as `null`,
+    /// which lets optional parameters be read as an `Option<T>`.
+    pub fn param<T: DeserializeOwned>(&self, index: usize) -> Result<T> {
+        let val = self.params.get(index).cloned().unwrap_or(Value::Null);
+
+        #[cfg(not(feature = "debug"))]
+        return Ok(T::deserialize(val)?);
+
+        #[cfg(feature = "debug")]
+        return Ok(serde_path_to_error::deserialize(val)?);
+    }
+}
+
+/// Receives the forwarded events of one or more event names from a single computer.
+#[derive(Debug)]
+pub struct EventReceiver {
+    names: Vec<String>,
+    rx: Receiver<Event>,
+}
+
+impl EventReceiver {
+    pub(crate) fn new(names: Vec<String>, rx: Receiver<Event>) -> Self {
+        Self { names, rx }
+    }
+
+    /// The event names this receiver is listening for.
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Waits for the next matching event.
+    ///
+    /// Returns [`Error::Disconnected`] once the computer has disconnected.
+    pub async fn recv(&mut self) -> Result<Event> {
+        loop {
+            match self.rx.recv().await {
+                Ok(event) if self.names.contains(&event.name) => return Ok(event),
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("Event receiver lagged behind, skipped {} events", skipped);
+                }
+                Err(RecvError::Closed) => return debug_feature!(Err(Error::Disconnected)),
+            }
+        }
+    }
+
+    /// Converts this receiver into a stream that ends when the computer disconnects.
+    pub fn into_stream(self) -> impl Stream<Item = Event> + Send + Unpin {
+        Box::pin(futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.ok().map(|event| (event, rx))
+        }))
+    }
+}
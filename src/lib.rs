@@ -1,12 +1,13 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex as StdMutex};
 
-use computer::Computer;
+use computer::{Computer, WeakComputer};
 use error::{Error, Result};
 use response::CCResponse;
 use tokio::{
     net::ToSocketAddrs,
     select,
     sync::{
+        broadcast,
         mpsc::{unbounded_channel, UnboundedReceiver},
         Mutex,
     },
@@ -18,8 +19,11 @@ extern crate tracing;
 #[macro_use]
 extern crate serde;
 
+pub mod api;
 pub mod computer;
 pub mod error;
+pub mod event;
+pub mod lua_compat;
 pub mod peripheral;
 pub mod protocol;
 mod request;
@@ -31,6 +35,7 @@ pub mod wrappers;
 
 pub struct Server {
     inner: Arc<Mutex<ServerInner>>,
+    registry: Arc<Registry>,
 }
 
 impl Server {
@@ -39,8 +44,24 @@ impl Server {
     }
 
     pub fn listen_on(addr: impl ToSocketAddrs + Send + 'static) -> Self {
+        let registry = Arc::new(Registry::new());
         Self {
-            inner: Arc::new(Mutex::new(ServerInner::spawn_new(addr))),
+            inner: Arc::new(Mutex::new(ServerInner::spawn_new(addr, registry.clone()))),
+            registry,
+        }
+    }
+
+    /// Every computer that is currently connected, whether or not it has been
+    /// taken with [`Server::wait_for_connection`].
+    pub fn computers(&self) -> Vec<Computer> {
+        self.registry.computers()
+    }
+
+    /// Receives every computer that connects from now on. Unlike [`Server::wait_for_connection`],
+    /// this does not take the computer out of the connection queue.
+    pub fn connections(&self) -> Connections {
+        Connections {
+            rx: self.registry.connected.subscribe(),
         }
     }
 
@@ -89,6 +110,36 @@ impl Server {
 
         Ok(computer)
     }
+
+    /// Waits for the computer with the given ID to connect, returning it right away if it
+    /// already is. Like [`Server::connections`], this does not take it out of the connection queue.
+    pub async fn wait_for_connection_with_id(&self, id: u64) -> Result<Computer> {
+        // subscribe first so a computer connecting while we search isn't missed
+        let mut connections = self.connections();
+        let has_id = |computer: &Computer| {
+            computer
+                .computer_info()
+                .is_ok_and(|info| info.id == Some(id))
+        };
+
+        if let Some(computer) = self.computers().into_iter().find(has_id) {
+            return Ok(computer);
+        }
+
+        loop {
+            match connections.recv().await {
+                Ok(computer) if has_id(&computer) => return Ok(computer),
+                Ok(_) => {}
+                // it may have been one of the computers that were skipped
+                Err(err) if matches!(error::as_error(&err), Some(Error::ConnectionsLagged(_))) => {
+                    if let Some(computer) = self.computers().into_iter().find(has_id) {
+                        return Ok(computer);
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
 }
 
 struct ServerInner {
@@ -104,9 +155,9 @@ impl Drop for ServerInner {
 }
 
 impl ServerInner {
-    pub fn spawn_new(addr: impl ToSocketAddrs + Send + 'static) -> Self {
+    pub fn spawn_new(addr: impl ToSocketAddrs + Send + 'static, registry: Arc<Registry>) -> Self {
         let (tx, rx) = unbounded_channel();
-        let handle = tokio::spawn(socket::socket_thread(addr, tx));
+        let handle = tokio::spawn(socket::socket_thread(addr, tx, registry));
         Self {
             handle,
             rx,
@@ -115,6 +166,67 @@ impl ServerInner {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct Registry {
+    // weak so the registry doesn't keep computers connected after every handle is dropped
+    computers: StdMutex<Vec<WeakComputer>>,
+    connected: broadcast::Sender<WeakComputer>,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let (connected, _) = broadcast::channel(64);
+        Self {
+            computers: StdMutex::new(Vec::new()),
+            connected,
+        }
+    }
+
+    pub(crate) fn register(&self, computer: &Computer) {
+        let mut computers = self.computers.lock().unwrap();
+        computers.retain(|computer| computer.upgrade().is_some());
+        computers.push(computer.downgrade());
+        // nobody listening is fine, they can still find it in the list
+        let _ = self.connected.send(computer.downgrade());
+    }
+
+    fn computers(&self) -> Vec<Computer> {
+        let mut computers = self.computers.lock().unwrap();
+        computers.retain(|computer| computer.upgrade().is_some());
+        computers.iter().filter_map(WeakComputer::upgrade).collect()
+    }
+}
+
+/// Receives newly connected computers, see [`Server::connections`].
+#[derive(Debug)]
+pub struct Connections {
+    rx: broadcast::Receiver<WeakComputer>,
+}
+
+impl Connections {
+    /// Waits for the next computer to connect. If this falls too far behind, the computers it
+    /// missed are skipped and [`Error::ConnectionsLagged`] is returned once, after which
+    /// receiving carries on. The missed computers can still be found with [`Server::computers`].
+    pub async fn recv(&mut self) -> Result<Computer> {
+        loop {
+            match self.rx.recv().await {
+                Ok(computer) => {
+                    // skip computers that were dropped or disconnected before we got to them
+                    if let Some(computer) = computer.upgrade() {
+                        return Ok(computer);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    return debug_feature!(Err(Error::ConnectionsLagged(skipped)))
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    return debug_feature!(Err(Error::ServerThreadFailed))
+                }
+            }
+        }
+    }
+}
+
 #[cfg(feature = "debug")]
 macro_rules! debug_feature {
     ($result:expr) => {
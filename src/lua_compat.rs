@@ -2,7 +2,10 @@
 //! In places where we are specifically expecting an array, we can use this deserializer to ensure
 //! serde doesn't get hung up on the fact that our "array" is actually an empty object.
 
-use std::ops::{Deref, DerefMut};
+use std::{
+    collections::BTreeMap,
+    ops::{Deref, DerefMut},
+};
 
 use serde::de::{Deserialize, DeserializeOwned, Deserializer, Error, Visitor};
 use serde_json::Value;
@@ -11,6 +14,12 @@ use serde_json::Value;
 #[serde(transparent)]
 pub struct LuaVec<T: DeserializeOwned>(#[serde(deserialize_with = "deserialize_with")] pub Vec<T>);
 
+impl<T: DeserializeOwned> Default for LuaVec<T> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
 impl<T: DeserializeOwned> Deref for LuaVec<T> {
     type Target = Vec<T>;
 
@@ -25,6 +34,29 @@ impl<T: DeserializeOwned> DerefMut for LuaVec<T> {
     }
 }
 
+/// A Lua table with holes in it (i.e. the slots of an inventory), keyed by its 1-based Lua indices.
+///
+/// These are serialized as an array with `null` in the holes, or an empty object if every index is empty.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(transparent)]
+pub struct LuaSparseVec<T: DeserializeOwned>(
+    #[serde(deserialize_with = "deserialize_sparse_with")] pub BTreeMap<usize, T>,
+);
+
+impl<T: DeserializeOwned> Deref for LuaSparseVec<T> {
+    type Target = BTreeMap<usize, T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: DeserializeOwned> DerefMut for LuaSparseVec<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 #[derive(Debug)]
 pub(crate) enum EmptyVecOrEmptyObject<T> {
     Vec(Vec<T>),
@@ -48,6 +80,17 @@ pub fn deserialize_with<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
     Ok(EmptyVecOrEmptyObject::deserialize(d)?.into_vec())
 }
 
+pub fn deserialize_sparse_with<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    d: D,
+) -> Result<BTreeMap<usize, T>, D::Error> {
+    Ok(EmptyVecOrEmptyObject::<Option<T>>::deserialize(d)?
+        .into_vec()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, value)| value.map(|value| (i + 1, value)))
+        .collect())
+}
+
 impl<'de, T> Visitor<'de> for EmptyVecOrEmptyObjectVisitor<T>
 where
     T: Deserialize<'de>,
@@ -66,7 +109,7 @@ where
             Some(size) if size > 0 => Err(A::Error::custom(
                 "expected an empty object, found a non-empty object",
             )),
-            Some(size) if size == 0 => Ok(EmptyVecOrEmptyObject::Object),
+            Some(0) => Ok(EmptyVecOrEmptyObject::Object),
             _ => {
                 if map.next_entry::<Value, Value>()?.is_some() {
                     Err(A::Error::custom(
@@ -1,15 +1,67 @@
+use std::sync::OnceLock;
+
 use serde::de::DeserializeOwned;
 use serde_json::Value;
 
-use crate::{computer::Computer, error::Result, request::PeripheralArgs};
+use crate::{
+    computer::{deserialize_optional, Computer},
+    debug_feature,
+    error::{Error, Result},
+    request::PeripheralArgs,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Peripheral<'a> {
     pub(crate) computer: &'a Computer,
     pub(crate) address: String,
+    // fetched on first use, a peripheral's types can't change while it's attached
+    pub(crate) types: OnceLock<Vec<String>>,
+}
+
+/// A typed wrapper around a peripheral of type [`PeripheralWrapper::TYPE`].
+pub trait PeripheralWrapper<'a>: Sized {
+    const TYPE: &'static str;
+
+    /// Wraps `peripheral` without checking its type.
+    fn wrap_unchecked(peripheral: Peripheral<'a>) -> Self;
 }
 
 impl<'a> Peripheral<'a> {
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Every type of the peripheral (i.e. `["minecraft:chest", "inventory"]`).
+    pub async fn types(&self) -> Result<&[String]> {
+        if self.types.get().is_none() {
+            let types = self
+                .computer
+                .get_peripheral_types(self.address.clone())
+                .await?;
+            let _ = self.types.set(types.0);
+        }
+
+        Ok(self.types.get().unwrap())
+    }
+
+    pub async fn has_type(&self, ty: &str) -> Result<bool> {
+        Ok(self.types().await?.iter().any(|t| t == ty))
+    }
+
+    /// Wraps this peripheral as `W` if it has `W`'s type. Unlike `into_wrapped`, this doesn't
+    /// consume the peripheral, so one peripheral can be wrapped several ways
+    /// (i.e. a tank that is both an inventory and a fluid storage).
+    pub async fn as_wrapper<W: PeripheralWrapper<'a>>(&self) -> Result<W> {
+        if !self.has_type(W::TYPE).await? {
+            return debug_feature!(Err(Error::WrongPeripheralType(
+                self.types().await?.join(", "),
+                W::TYPE.into(),
+            )));
+        }
+
+        Ok(W::wrap_unchecked(self.clone()))
+    }
+
     pub async fn call_method<S: PeripheralArgs>(
         &self,
         method: impl Into<String>,
@@ -30,6 +82,14 @@ impl<'a> Peripheral<'a> {
             .await
     }
 
+    pub async fn call_method_with_optional<S: PeripheralArgs, T: DeserializeOwned>(
+        &self,
+        method: impl Into<String>,
+        args: S,
+    ) -> Result<Option<T>> {
+        deserialize_optional(self.call_method(method, args).await?)
+    }
+
     pub async fn call_method_with_raw<S: PeripheralArgs, T: DeserializeOwned>(
         &self,
         method: impl Into<String>,
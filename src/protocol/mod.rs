@@ -34,4 +34,14 @@ define_protocol! {
         result: Option<Vec<serde_json::Value>>,
     };
     GetPeripheralType = (String) => (String);
+    CallGlobal = {
+        api: String,
+        method: String,
+        args: serde_json::Value,
+    } => {
+        success: bool,
+        error: Option<Vec<serde_json::Value>>,
+        result: Option<Vec<serde_json::Value>>,
+    };
+    SubscribeEvent = (String) => (bool);
 }
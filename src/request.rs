@@ -52,6 +52,12 @@ pub enum CCRequestKind {
         args: Box<dyn PeripheralArgs>,
     },
     GetPeripheralType(String),
+    CallGlobal {
+        api: String,
+        method: String,
+        args: Box<dyn PeripheralArgs>,
+    },
+    SubscribeEvent(String),
 }
 
 pub trait PeripheralArgs: Serialize + Debug + Send + Sync + 'static {}
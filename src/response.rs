@@ -2,7 +2,7 @@ use thiserror::Error;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
-use crate::computer::ComputerInfo;
+use crate::{computer::ComputerInfo, event::Event, lua_compat::LuaVec};
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct CCResponse {
@@ -22,7 +22,14 @@ pub enum CCResponseKind {
         error: Option<Vec<serde_json::Value>>,
         result: Option<Vec<serde_json::Value>>,
     },
-    GetPeripheralType(String),
+    GetPeripheralType(LuaVec<String>),
+    CallGlobal {
+        success: bool,
+        error: Option<Vec<serde_json::Value>>,
+        result: Option<Vec<serde_json::Value>>,
+    },
+    SubscribeEvent(bool),
+    Event(Event),
 }
 
 #[derive(Debug, Error)]
@@ -1,11 +1,15 @@
+use std::sync::Arc;
 use thiserror::Error;
+
 use tokio::{
     net::{TcpListener, ToSocketAddrs},
     sync::mpsc::UnboundedSender,
 };
 use tokio_tungstenite::{accept_async, tungstenite::Error as WsError};
 
-use crate::{computer::Computer, error::Error};
+#[cfg(not(feature = "debug"))]
+use crate::error::Error;
+use crate::{computer::Computer, Registry};
 
 #[derive(Debug, Error)]
 pub enum SocketError {
@@ -23,16 +27,21 @@ pub enum SocketError {
     ComputerError(#[from] eyre::Report),
 }
 
-pub async fn socket_thread(addr: impl ToSocketAddrs, tx: UnboundedSender<Computer>) {
-    if let Err(e) = socket_thread_inner(addr, tx).await {
+pub async fn socket_thread(
+    addr: impl ToSocketAddrs,
+    tx: UnboundedSender<Computer>,
+    registry: Arc<Registry>,
+) {
+    if let Err(e) = socket_thread_inner(addr, tx, registry).await {
         error!("socket thread failed: {}", e);
     }
 }
 
-#[instrument(skip(addr, tx))]
+#[instrument(skip(addr, tx, registry))]
 pub async fn socket_thread_inner(
     addr: impl ToSocketAddrs,
     tx: UnboundedSender<Computer>,
+    registry: Arc<Registry>,
 ) -> Result<(), SocketError> {
     let server = TcpListener::bind(addr)
         .await
@@ -45,6 +54,7 @@ pub async fn socket_thread_inner(
             .await
             .map_err(SocketError::AcceptConnection)?;
         let computer = Computer::new(ws).await?;
+        registry.register(&computer);
         tx.send(computer).unwrap();
     }
 }
@@ -0,0 +1,275 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::Stream;
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{
+    error::Error,
+    event::{Event, EventReceiver},
+    wrappers::prelude::*,
+};
+
+mod text_component;
+pub use text_component::*;
+mod command_router;
+pub use command_router::*;
+
+/// The cooldown [`ChatBox`] waits between messages unless told otherwise with [`ChatBox::with_cooldown`].
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(1);
+
+// how many times a message is retried if the chat box is still on cooldown
+const MAX_ATTEMPTS: usize = 3;
+
+/// The optional arguments shared by every chat box method.
+#[derive(Debug, Clone, Default)]
+pub struct MessageOptions {
+    prefix: Option<String>,
+    brackets: Option<String>,
+    bracket_color: Option<String>,
+    range: Option<u32>,
+}
+
+impl MessageOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The text shown in the brackets before the message instead of `AP`.
+    pub fn prefix(self, prefix: impl ToString) -> Self {
+        Self {
+            prefix: Some(prefix.to_string()),
+            ..self
+        }
+    }
+
+    /// The brackets around the prefix, i.e. `[]` or `<>`.
+    pub fn brackets(self, brackets: impl ToString) -> Self {
+        Self {
+            brackets: Some(brackets.to_string()),
+            ..self
+        }
+    }
+
+    /// The color of the brackets as a formatting code, i.e. `&a`.
+    pub fn bracket_color(self, bracket_color: impl ToString) -> Self {
+        Self {
+            bracket_color: Some(bracket_color.to_string()),
+            ..self
+        }
+    }
+
+    /// Only players within `range` blocks receive the message.
+    pub fn range(self, range: u32) -> Self {
+        Self {
+            range: Some(range),
+            ..self
+        }
+    }
+
+    fn args(&self) -> [Value; 4] {
+        [
+            self.prefix.clone().into(),
+            self.brackets.clone().into(),
+            self.bracket_color.clone().into(),
+            self.range.into(),
+        ]
+    }
+}
+
+/// A message sent in chat, from the `chat` event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChatMessage {
+    pub username: String,
+    pub message: String,
+    pub uuid: Option<String>,
+    /// Messages starting with `$` are hidden from other players.
+    pub is_hidden: bool,
+}
+
+impl ChatMessage {
+    pub fn from_event(event: &Event) -> Result<Self> {
+        Ok(Self {
+            username: event.param(0)?,
+            message: event.param(1)?,
+            uuid: event.param(2)?,
+            is_hidden: event.param::<Option<bool>>(3)?.unwrap_or(false),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ChatReceiver {
+    events: EventReceiver,
+}
+
+impl ChatReceiver {
+    pub async fn recv(&mut self) -> Result<ChatMessage> {
+        ChatMessage::from_event(&self.events.recv().await?)
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = ChatMessage> + Send + Unpin {
+        Box::pin(futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.ok().map(|message| (message, rx))
+        }))
+    }
+}
+
+/// The Advanced Peripherals chat box.
+///
+/// Chat boxes can only send a message every so often. Every method waits for the cooldown
+/// to pass before sending, and retries if the chat box reports it is still on cooldown,
+/// so messages are only lost if they are retried too many times.
+#[derive(Debug, Clone)]
+pub struct ChatBox<'a> {
+    inner: Peripheral<'a>,
+    cooldown: Duration,
+    // shared between clones so they wait for each other
+    last_sent: Arc<Mutex<Option<Instant>>>,
+}
+
+impl<'a> PeripheralWrapper<'a> for ChatBox<'a> {
+    const TYPE: &'static str = "chatBox";
+
+    fn wrap_unchecked(peripheral: Peripheral<'a>) -> Self {
+        Self {
+            inner: peripheral,
+            cooldown: DEFAULT_COOLDOWN,
+            last_sent: Arc::new(Mutex::new(None)),
+        }
+    }
+}
+
+impl<'a> ChatBox<'a> {
+    /// Sets the time to wait between messages, which should match the server's `chatBoxCooldown` config.
+    pub fn with_cooldown(self, cooldown: Duration) -> Self {
+        Self { cooldown, ..self }
+    }
+
+    /// Sends a message to every player in range, returning false if it couldn't be sent
+    /// because the chat box stayed on cooldown.
+    pub async fn send_message(
+        &self,
+        message: impl ToString,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send("sendMessage", vec![message.to_string().into()], options)
+            .await
+    }
+
+    pub async fn send_message_to_player(
+        &self,
+        message: impl ToString,
+        username: impl ToString,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send(
+            "sendMessageToPlayer",
+            vec![message.to_string().into(), username.to_string().into()],
+            options,
+        )
+        .await
+    }
+
+    pub async fn send_toast_to_player(
+        &self,
+        message: impl ToString,
+        title: impl ToString,
+        username: impl ToString,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send(
+            "sendToastToPlayer",
+            vec![
+                message.to_string().into(),
+                title.to_string().into(),
+                username.to_string().into(),
+            ],
+            options,
+        )
+        .await
+    }
+
+    pub async fn send_formatted_message(
+        &self,
+        message: &TextComponent,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send(
+            "sendFormattedMessage",
+            vec![message.to_json().into()],
+            options,
+        )
+        .await
+    }
+
+    pub async fn send_formatted_message_to_player(
+        &self,
+        message: &TextComponent,
+        username: impl ToString,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send(
+            "sendFormattedMessageToPlayer",
+            vec![message.to_json().into(), username.to_string().into()],
+            options,
+        )
+        .await
+    }
+
+    pub async fn send_formatted_toast_to_player(
+        &self,
+        message: &TextComponent,
+        title: &TextComponent,
+        username: impl ToString,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        self.send(
+            "sendFormattedToastToPlayer",
+            vec![
+                message.to_json().into(),
+                title.to_json().into(),
+                username.to_string().into(),
+            ],
+            options,
+        )
+        .await
+    }
+
+    /// Subscribes to every message sent in chat from now on.
+    pub async fn messages(&self) -> Result<ChatReceiver> {
+        Ok(ChatReceiver {
+            events: self.inner.computer.subscribe("chat").await?,
+        })
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        mut args: Vec<Value>,
+        options: &MessageOptions,
+    ) -> Result<bool> {
+        args.extend(options.args());
+        while args.last() == Some(&Value::Null) {
+            args.pop();
+        }
+
+        // held until the message is sent so concurrent messages queue up
+        let mut last_sent = self.last_sent.lock().await;
+        for _ in 0..MAX_ATTEMPTS {
+            if let Some(last) = *last_sent {
+                tokio::time::sleep_until(last + self.cooldown).await;
+            }
+
+            let ret = self.inner.call_method(method, args.clone()).await?;
+            *last_sent = Some(Instant::now());
+
+            match &ret[..] {
+                [Value::Bool(true), ..] => return Ok(true),
+                [_, Value::String(err)] if err.contains("too often") => continue,
+                _ => return crate::debug_feature!(Err(Error::LuaError(ret))),
+            }
+        }
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,397 @@
+use std::{collections::HashSet, fmt::Display, future::Future, pin::Pin, str::FromStr};
+
+use thiserror::Error;
+
+use crate::error::Result;
+
+use super::{ChatBox, ChatMessage, MessageOptions};
+
+/// Why a command failed, sent back to the player who ran it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CommandError {
+    #[error("Unknown command {0:?}")]
+    UnknownCommand(String),
+    #[error("You don't have permission to use {0:?}")]
+    PermissionDenied(String),
+    #[error("Missing an argument, usage: {usage}")]
+    MissingArgument { index: usize, usage: String },
+    #[error("Invalid argument {value:?}, expected {expected}")]
+    InvalidArgument { value: String, expected: String },
+    #[error("{0}")]
+    Failed(String),
+}
+
+impl CommandError {
+    pub fn failed(err: impl Display) -> Self {
+        Self::Failed(err.to_string())
+    }
+}
+
+/// The arguments after a command's name, split on whitespace unless in double quotes. A backslash
+/// escapes the character after it, and an unterminated quote runs to the end of the message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandArgs {
+    args: Vec<String>,
+    usage: String,
+}
+
+impl CommandArgs {
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    pub fn raw(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Parses the argument at `index`, which must be present.
+    pub fn get<T: FromStr>(&self, index: usize) -> std::result::Result<T, CommandError> {
+        self.optional(index)?
+            .ok_or_else(|| CommandError::MissingArgument {
+                index,
+                usage: self.usage.clone(),
+            })
+    }
+
+    /// Parses the argument at `index`, returning `None` if there aren't that many arguments.
+    pub fn optional<T: FromStr>(
+        &self,
+        index: usize,
+    ) -> std::result::Result<Option<T>, CommandError> {
+        let Some(value) = self.args.get(index) else {
+            return Ok(None);
+        };
+
+        value
+            .parse()
+            .map(Some)
+            .map_err(|_| CommandError::InvalidArgument {
+                value: value.clone(),
+                expected: Self::type_description::<T>(),
+            })
+    }
+
+    /// Every argument from `index` on, joined with spaces.
+    pub fn rest(&self, index: usize) -> String {
+        self.args.get(index..).unwrap_or_default().join(" ")
+    }
+
+    // i.e. `u32` rather than `core::primitive::u32`
+    fn type_description<T>() -> String {
+        let name = std::any::type_name::<T>();
+        name.rsplit("::").next().unwrap_or(name).to_string()
+    }
+
+    fn parse(input: &str, usage: String) -> Self {
+        let mut args = Vec::new();
+        let mut current = String::new();
+        let mut in_quotes = false;
+        let mut has_arg = false;
+
+        let mut chars = input.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    current.push(chars.next().unwrap_or('\\'));
+                    has_arg = true;
+                }
+                '"' => {
+                    in_quotes = !in_quotes;
+                    has_arg = true;
+                }
+                c if c.is_whitespace() && !in_quotes => {
+                    if has_arg {
+                        args.push(std::mem::take(&mut current));
+                        has_arg = false;
+                    }
+                }
+                c => {
+                    current.push(c);
+                    has_arg = true;
+                }
+            }
+        }
+        if has_arg {
+            args.push(current);
+        }
+
+        Self { args, usage }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CommandContext {
+    pub message: ChatMessage,
+    pub args: CommandArgs,
+}
+
+impl CommandContext {
+    pub fn username(&self) -> &str {
+        &self.message.username
+    }
+
+    pub fn uuid(&self) -> Option<&str> {
+        self.message.uuid.as_deref()
+    }
+}
+
+pub type CommandResult = std::result::Result<String, CommandError>;
+
+type Handler<'h> = Box<
+    dyn Fn(CommandContext) -> Pin<Box<dyn Future<Output = CommandResult> + Send + 'h>>
+        + Send
+        + Sync
+        + 'h,
+>;
+
+/// A chat command. The handler's reply is sent to the player who ran the command, unless it is empty.
+pub struct Command<'h> {
+    name: String,
+    usage: String,
+    description: String,
+    // lowercase UUIDs, `None` lets anyone run the command
+    allowed: Option<HashSet<String>>,
+    handler: Handler<'h>,
+}
+
+impl<'h> Command<'h> {
+    pub fn new<F, Fut>(name: impl ToString, handler: F) -> Self
+    where
+        F: Fn(CommandContext) -> Fut + Send + Sync + 'h,
+        Fut: Future<Output = CommandResult> + Send + 'h,
+    {
+        Self {
+            name: name.to_string().to_lowercase(),
+            usage: String::new(),
+            description: String::new(),
+            allowed: None,
+            handler: Box::new(move |ctx| Box::pin(handler(ctx))),
+        }
+    }
+
+    /// The arguments shown in help and errors, i.e. `<item> [count]`.
+    pub fn usage(self, usage: impl ToString) -> Self {
+        Self {
+            usage: usage.to_string(),
+            ..self
+        }
+    }
+
+    pub fn description(self, description: impl ToString) -> Self {
+        Self {
+            description: description.to_string(),
+            ..self
+        }
+    }
+
+    /// Only lets the players with the given UUIDs run the command. UUIDs are used rather than
+    /// usernames since a player can change their name. Can be called more than once.
+    pub fn allow(mut self, uuids: impl IntoIterator<Item = impl ToString>) -> Self {
+        self.allowed.get_or_insert_with(HashSet::new).extend(
+            uuids
+                .into_iter()
+                .map(|uuid| uuid.to_string().to_lowercase()),
+        );
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the player with `uuid` can run the command. A player without a UUID
+    /// can only run commands that everyone can.
+    pub fn is_allowed(&self, uuid: Option<&str>) -> bool {
+        match (&self.allowed, uuid) {
+            (None, _) => true,
+            (Some(allowed), Some(uuid)) => allowed.contains(&uuid.to_lowercase()),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+impl std::fmt::Debug for Command<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("usage", &self.usage)
+            .field("description", &self.description)
+            .field("allowed", &self.allowed)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Runs [`Command`]s from chat messages starting with a prefix (i.e. `!stock iron`).
+///
+/// A `help` command listing the commands a player can run is added unless one is registered.
+#[derive(Debug)]
+pub struct CommandRouter<'h> {
+    prefix: String,
+    commands: Vec<Command<'h>>,
+    reply_options: MessageOptions,
+}
+
+impl<'h> CommandRouter<'h> {
+    pub fn new(prefix: impl ToString) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+            commands: Vec::new(),
+            reply_options: MessageOptions::default(),
+        }
+    }
+
+    pub fn register(mut self, command: Command<'h>) -> Self {
+        self.commands.push(command);
+        self
+    }
+
+    /// The options replies are sent with, i.e. to change the prefix they are shown with.
+    pub fn reply_options(self, reply_options: MessageOptions) -> Self {
+        Self {
+            reply_options,
+            ..self
+        }
+    }
+
+    /// Runs the command in `message`, returning the reply for the player, or `None` if the
+    /// message isn't a command.
+    pub async fn dispatch(&self, message: ChatMessage) -> Option<CommandResult> {
+        let input = message.message.trim().strip_prefix(&self.prefix)?;
+        let (name, args) = input.split_once(char::is_whitespace).unwrap_or((input, ""));
+        let name = name.to_lowercase();
+
+        let Some(command) = self.commands.iter().find(|command| command.name == name) else {
+            if name == "help" {
+                return Some(Ok(self.help(message.uuid.as_deref())));
+            }
+            return Some(Err(CommandError::UnknownCommand(name)));
+        };
+
+        if !command.is_allowed(message.uuid.as_deref()) {
+            return Some(Err(CommandError::PermissionDenied(name)));
+        }
+
+        let usage = format!("{}{} {}", self.prefix, command.name, command.usage);
+        let ctx = CommandContext {
+            args: CommandArgs::parse(args, usage.trim_end().to_string()),
+            message,
+        };
+
+        Some((command.handler)(ctx).await)
+    }
+
+    /// Runs the command in `message` and replies to the player who sent it. Returns
+    /// false if the message isn't a command.
+    pub async fn handle(&self, chat_box: &ChatBox<'_>, message: ChatMessage) -> Result<bool> {
+        let username = message.username.clone();
+        let reply = match self.dispatch(message).await {
+            Some(Ok(reply)) => reply,
+            Some(Err(err)) => err.to_string(),
+            None => return Ok(false),
+        };
+
+        if !reply.is_empty() {
+            chat_box
+                .send_message_to_player(reply, username, &self.reply_options)
+                .await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Handles every chat message from now on, only returning if receiving messages fails
+    /// (i.e. the computer disconnecting). Replies that can't be sent are logged and skipped.
+    pub async fn run(&self, chat_box: &ChatBox<'_>) -> Result<()> {
+        let mut messages = chat_box.messages().await?;
+        loop {
+            let message = messages.recv().await?;
+            let username = message.username.clone();
+            if let Err(err) = self.handle(chat_box, message).await {
+                warn!("Failed to reply to a command from {}: {}", username, err);
+            }
+        }
+    }
+
+    fn help(&self, uuid: Option<&str>) -> String {
+        self.commands
+            .iter()
+            .filter(|command| command.is_allowed(uuid))
+            .map(|command| {
+                let mut line = format!("{}{}", self.prefix, command.name);
+                if !command.usage.is_empty() {
+                    line = format!("{line} {}", command.usage);
+                }
+                if !command.description.is_empty() {
+                    line = format!("{line} - {}", command.description);
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Vec<String> {
+        CommandArgs::parse(input, String::new()).args
+    }
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(parse("  iron   64 "), ["iron", "64"]);
+        assert!(parse("   ").is_empty());
+    }
+
+    #[test]
+    fn keeps_quoted_arguments_together() {
+        assert_eq!(
+            parse(r#"say "hello there" now"#),
+            ["say", "hello there", "now"]
+        );
+        assert_eq!(parse(r#"a"b c"d"#), ["ab cd"]);
+        assert_eq!(parse(r#""" x"#), ["", "x"]);
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(parse(r#"\"quoted\" a\ b"#), [r#""quoted""#, "a b"]);
+        assert_eq!(parse(r#""say \"hi\"" \\"#), [r#"say "hi""#, "\\"]);
+        assert_eq!(parse(r"trailing\"), ["trailing\\"]);
+    }
+
+    #[test]
+    fn unterminated_quotes_run_to_the_end() {
+        assert_eq!(parse(r#"a "b c  d"#), ["a", "b c  d"]);
+        assert_eq!(parse(r#"a ""#), ["a", ""]);
+    }
+
+    #[test]
+    fn parses_typed_arguments() {
+        let args = CommandArgs::parse("iron 64 x", "!stock <item> <count>".to_string());
+        assert_eq!(args.get::<String>(0), Ok("iron".to_string()));
+        assert_eq!(args.get::<u32>(1), Ok(64));
+        assert_eq!(args.optional::<u32>(3), Ok(None));
+        assert_eq!(
+            args.get::<u32>(2),
+            Err(CommandError::InvalidArgument {
+                value: "x".to_string(),
+                expected: "u32".to_string(),
+            })
+        );
+        assert_eq!(
+            args.get::<u32>(3),
+            Err(CommandError::MissingArgument {
+                index: 3,
+                usage: "!stock <item> <count>".to_string(),
+            })
+        );
+        assert_eq!(args.rest(1), "64 x");
+    }
+}
@@ -0,0 +1,216 @@
+use serde::{Serialize, Serializer};
+
+/// A color for [`TextComponent::color`], either one of Minecraft's named colors or any RGB color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextColor {
+    Black,
+    DarkBlue,
+    DarkGreen,
+    DarkAqua,
+    DarkRed,
+    DarkPurple,
+    Gold,
+    Gray,
+    DarkGray,
+    Blue,
+    Green,
+    Aqua,
+    Red,
+    LightPurple,
+    Yellow,
+    White,
+    /// A `0xRRGGBB` color, only supported by Minecraft 1.16 and later.
+    Rgb(u32),
+}
+
+impl TextColor {
+    pub fn name(&self) -> String {
+        match self {
+            TextColor::Black => "black".into(),
+            TextColor::DarkBlue => "dark_blue".into(),
+            TextColor::DarkGreen => "dark_green".into(),
+            TextColor::DarkAqua => "dark_aqua".into(),
+            TextColor::DarkRed => "dark_red".into(),
+            TextColor::DarkPurple => "dark_purple".into(),
+            TextColor::Gold => "gold".into(),
+            TextColor::Gray => "gray".into(),
+            TextColor::DarkGray => "dark_gray".into(),
+            TextColor::Blue => "blue".into(),
+            TextColor::Green => "green".into(),
+            TextColor::Aqua => "aqua".into(),
+            TextColor::Red => "red".into(),
+            TextColor::LightPurple => "light_purple".into(),
+            TextColor::Yellow => "yellow".into(),
+            TextColor::White => "white".into(),
+            TextColor::Rgb(rgb) => format!("#{:06X}", rgb & 0xFFFFFF),
+        }
+    }
+}
+
+impl Serialize for TextColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClickAction {
+    OpenUrl,
+    RunCommand,
+    SuggestCommand,
+    CopyToClipboard,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct HoverEvent {
+    action: &'static str,
+    contents: Box<TextComponent>,
+}
+
+/// A Minecraft JSON text component, for the chat box's formatted messages.
+///
+/// ```
+/// use computercraft::wrappers::ap::chat_box::{TextColor, TextComponent};
+///
+/// let message = TextComponent::text("Storage is ")
+///     .append(TextComponent::text("90% full").color(TextColor::Red).bold())
+///     .hover_text(TextComponent::text("Click to open the dashboard"))
+///     .open_url("https://example.com");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextComponent {
+    text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    color: Option<TextColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bold: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    italic: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    underlined: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    strikethrough: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    obfuscated: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    click_event: Option<ClickEvent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hover_event: Option<HoverEvent>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    extra: Vec<TextComponent>,
+}
+
+impl TextComponent {
+    pub fn text(text: impl ToString) -> Self {
+        Self {
+            text: text.to_string(),
+            ..Self::default()
+        }
+    }
+
+    pub fn color(self, color: TextColor) -> Self {
+        Self {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Self {
+            bold: Some(true),
+            ..self
+        }
+    }
+
+    pub fn italic(self) -> Self {
+        Self {
+            italic: Some(true),
+            ..self
+        }
+    }
+
+    pub fn underlined(self) -> Self {
+        Self {
+            underlined: Some(true),
+            ..self
+        }
+    }
+
+    pub fn strikethrough(self) -> Self {
+        Self {
+            strikethrough: Some(true),
+            ..self
+        }
+    }
+
+    pub fn obfuscated(self) -> Self {
+        Self {
+            obfuscated: Some(true),
+            ..self
+        }
+    }
+
+    pub fn click_event(self, action: ClickAction, value: impl ToString) -> Self {
+        Self {
+            click_event: Some(ClickEvent {
+                action,
+                value: value.to_string(),
+            }),
+            ..self
+        }
+    }
+
+    pub fn open_url(self, url: impl ToString) -> Self {
+        self.click_event(ClickAction::OpenUrl, url)
+    }
+
+    /// Runs `command` (including the leading `/`) as the player who clicked.
+    pub fn run_command(self, command: impl ToString) -> Self {
+        self.click_event(ClickAction::RunCommand, command)
+    }
+
+    pub fn suggest_command(self, command: impl ToString) -> Self {
+        self.click_event(ClickAction::SuggestCommand, command)
+    }
+
+    /// Shows `text` when the component is hovered over.
+    pub fn hover_text(self, text: TextComponent) -> Self {
+        Self {
+            hover_event: Some(HoverEvent {
+                action: "show_text",
+                contents: Box::new(text),
+            }),
+            ..self
+        }
+    }
+
+    /// Adds a component after this one, which inherits this component's formatting.
+    pub fn append(mut self, component: TextComponent) -> Self {
+        self.extra.push(component);
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("text components are always valid JSON")
+    }
+}
+
+impl From<&str> for TextComponent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<String> for TextComponent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
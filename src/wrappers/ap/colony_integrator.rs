@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use crate::wrappers::{lua_compat::LuaVec, prelude::*};
 
 mod citizen;
@@ -6,6 +8,12 @@ mod visitor;
 pub use visitor::*;
 mod request;
 pub use request::*;
+mod building;
+pub use building::*;
+mod work_order;
+pub use work_order::*;
+mod research;
+pub use research::*;
 
 generate_wrapper_impl!(ColonyIntegrator = "colonyIntegrator");
 
@@ -26,4 +34,99 @@ impl<'a> ColonyIntegrator<'a> {
             .call_method_with("getRequests", Value::Null)
             .await
     }
+
+    pub async fn get_visitors(&self) -> Result<LuaVec<Visitor>> {
+        self.inner
+            .call_method_with("getVisitors", Value::Null)
+            .await
+    }
+
+    pub async fn get_buildings(&self) -> Result<LuaVec<Building>> {
+        self.inner
+            .call_method_with("getBuildings", Value::Null)
+            .await
+    }
+
+    pub async fn get_work_orders(&self) -> Result<LuaVec<WorkOrder>> {
+        self.inner
+            .call_method_with("getWorkOrders", Value::Null)
+            .await
+    }
+
+    /// The resources needed for a work order, `None` if there is no work order with that ID.
+    pub async fn get_work_order_resources(
+        &self,
+        id: u64,
+    ) -> Result<Option<LuaVec<BuilderResource>>> {
+        self.inner
+            .call_method_with_optional("getWorkOrderResources", id)
+            .await
+    }
+
+    /// The resources needed by the builder whose hut is at `hut`, `None` if there is no builder's hut there.
+    pub async fn get_builder_resources(
+        &self,
+        hut: Position,
+    ) -> Result<Option<LuaVec<BuilderResource>>> {
+        self.inner
+            .call_method_with_optional("getBuilderResources", vec![hut])
+            .await
+    }
+
+    /// The colony's research tree, keyed by branch.
+    pub async fn get_research(&self) -> Result<HashMap<String, LuaVec<Research>>> {
+        self.inner
+            .call_method_with("getResearch", Value::Null)
+            .await
+    }
+
+    pub async fn get_colony_name(&self) -> Result<String> {
+        self.inner
+            .call_method_with("getColonyName", Value::Null)
+            .await
+    }
+
+    pub async fn get_colony_id(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getColonyID", Value::Null)
+            .await
+    }
+
+    /// The colony's style pack (i.e. `medievaloak`).
+    pub async fn get_style(&self) -> Result<String> {
+        self.inner
+            .call_method_with("getColonyStyle", Value::Null)
+            .await
+    }
+
+    /// The colony's average happiness, between 0 and 10.
+    pub async fn get_happiness(&self) -> Result<f64> {
+        self.inner
+            .call_method_with("getHappiness", Value::Null)
+            .await
+    }
+
+    pub async fn amount_of_citizens(&self) -> Result<u32> {
+        self.inner
+            .call_method_with("amountOfCitizens", Value::Null)
+            .await
+    }
+
+    pub async fn max_of_citizens(&self) -> Result<u32> {
+        self.inner
+            .call_method_with("maxOfCitizens", Value::Null)
+            .await
+    }
+
+    generate_wrapped_fn!(
+        is_under_attack -> bool = | | => isUnderAttack(Value::Null);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    /// The position of the colony's town hall.
+    pub async fn get_location(&self) -> Result<Position> {
+        self.inner
+            .call_method_with("getLocation", Value::Null)
+            .await
+    }
 }
@@ -0,0 +1,41 @@
+use crate::wrappers::lua_compat::LuaVec;
+
+use super::Position;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildingCitizen {
+    pub id: u64,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Footprint {
+    pub corner1: Position,
+    pub corner2: Position,
+    pub rotation: i64,
+    pub mirror: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Building {
+    pub location: Position,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub style: String,
+    pub name: String,
+    pub level: u32,
+    pub max_level: u32,
+    pub built: bool,
+    /// Whether the building is being built or upgraded.
+    pub wip: bool,
+    pub priority: i64,
+    #[serde(default)]
+    pub guarded: bool,
+    #[serde(default)]
+    pub storage_blocks: u32,
+    #[serde(default)]
+    pub storage_slots: u32,
+    pub citizens: LuaVec<BuildingCitizen>,
+    pub footprint: Option<Footprint>,
+}
@@ -0,0 +1,19 @@
+use serde_json::Value;
+
+use crate::wrappers::lua_compat::LuaVec;
+
+/// A research in a branch of the colony's research tree, with the research it unlocks as children.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Research {
+    pub id: String,
+    pub name: String,
+    pub status: String,
+    #[serde(default)]
+    pub requirements: LuaVec<Value>,
+    #[serde(default)]
+    pub cost: LuaVec<Value>,
+    #[serde(default)]
+    pub effects: LuaVec<String>,
+    #[serde(default)]
+    pub children: LuaVec<Research>,
+}
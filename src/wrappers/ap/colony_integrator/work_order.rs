@@ -0,0 +1,40 @@
+use super::Position;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkOrder {
+    pub id: u64,
+    pub work_order_type: String,
+    pub building_name: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub priority: i64,
+    pub is_claimed: bool,
+    #[serde(default)]
+    pub changed: bool,
+    pub location: Position,
+    /// The position of the builder's hut that claimed the work order.
+    pub builder: Option<Position>,
+    pub target_level: Option<u32>,
+}
+
+/// An item a builder needs for a work order, from `getWorkOrderResources` or `getBuilderResources`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuilderResource {
+    pub item: String,
+    pub display_name: String,
+    pub needed: u32,
+    pub available: u32,
+    pub delivering: u32,
+    pub status: String,
+}
+
+impl BuilderResource {
+    /// The amount still to be found, taking what is available and on its way into account.
+    pub fn shortfall(&self) -> u32 {
+        self.needed
+            .saturating_sub(self.available)
+            .saturating_sub(self.delivering)
+    }
+}
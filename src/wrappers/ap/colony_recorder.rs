@@ -0,0 +1,559 @@
+use std::{
+    collections::{HashMap, HashSet},
+    io::SeekFrom,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use futures_util::Stream;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::error::Result;
+
+use super::colony_integrator::{Building, Citizen, ColonyIntegrator, ColonyRequest, Position};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CitizenSnapshot {
+    pub id: String,
+    pub name: String,
+    pub happiness: f64,
+    pub saturation: f64,
+    pub health: Option<f64>,
+    pub job: Option<String>,
+    /// Skill levels by skill name.
+    pub skills: HashMap<String, f64>,
+}
+
+impl From<&Citizen> for CitizenSnapshot {
+    fn from(citizen: &Citizen) -> Self {
+        Self {
+            id: citizen.id.clone(),
+            name: citizen.name.clone(),
+            happiness: citizen.happiness,
+            saturation: citizen.saturation,
+            health: citizen.health,
+            job: citizen.work.as_ref().map(|work| work.job.clone()),
+            skills: citizen
+                .skills
+                .iter()
+                .map(|(name, skill)| (name.clone(), skill.level))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildingSnapshot {
+    pub kind: String,
+    pub name: String,
+    pub location: Position,
+    pub level: u32,
+    pub built: bool,
+    pub wip: bool,
+}
+
+impl BuildingSnapshot {
+    // buildings don't have IDs, but only one can be at a position
+    fn key(&self) -> (i64, i64, i64) {
+        (
+            self.location.x as i64,
+            self.location.y as i64,
+            self.location.z as i64,
+        )
+    }
+}
+
+impl From<&Building> for BuildingSnapshot {
+    fn from(building: &Building) -> Self {
+        Self {
+            kind: building.kind.clone(),
+            name: building.name.clone(),
+            location: building.location.clone(),
+            level: building.level,
+            built: building.built,
+            wip: building.wip,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestSnapshot {
+    pub id: String,
+    pub name: String,
+    pub count: usize,
+    pub state: String,
+    /// When the request was first seen, in seconds since the Unix epoch.
+    pub first_seen: u64,
+}
+
+/// The state of a colony at one point in time, one line of a recorder's file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColonySnapshot {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub citizens: Vec<CitizenSnapshot>,
+    /// Visitors as (ID, name).
+    pub visitors: Vec<(String, String)>,
+    pub buildings: Vec<BuildingSnapshot>,
+    pub requests: Vec<RequestSnapshot>,
+}
+
+/// Statistics of a snapshot for dashboards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColonySummary {
+    pub timestamp: u64,
+    pub citizens: usize,
+    pub visitors: usize,
+    pub average_happiness: f64,
+    pub average_saturation: f64,
+    /// Only citizens that report their health are counted.
+    pub average_health: Option<f64>,
+    pub unemployed: usize,
+    pub buildings: usize,
+    pub buildings_in_progress: usize,
+    pub open_requests: usize,
+}
+
+impl ColonySnapshot {
+    pub fn summary(&self) -> ColonySummary {
+        let average = |values: Vec<f64>| match values.len() {
+            0 => None,
+            len => Some(values.iter().sum::<f64>() / len as f64),
+        };
+
+        ColonySummary {
+            timestamp: self.timestamp,
+            citizens: self.citizens.len(),
+            visitors: self.visitors.len(),
+            average_happiness: average(self.citizens.iter().map(|c| c.happiness).collect())
+                .unwrap_or(0.0),
+            average_saturation: average(self.citizens.iter().map(|c| c.saturation).collect())
+                .unwrap_or(0.0),
+            average_health: average(self.citizens.iter().filter_map(|c| c.health).collect()),
+            unemployed: self.citizens.iter().filter(|c| c.job.is_none()).count(),
+            buildings: self.buildings.len(),
+            buildings_in_progress: self.buildings.iter().filter(|b| b.wip).count(),
+            open_requests: self.requests.len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColonyEvent {
+    CitizenJoined {
+        id: String,
+        name: String,
+    },
+    /// The citizen is gone, usually because they died.
+    CitizenLost {
+        id: String,
+        name: String,
+    },
+    NewVisitor {
+        id: String,
+        name: String,
+    },
+    BuildingPlaced {
+        name: String,
+        location: Position,
+    },
+    BuildingRemoved {
+        name: String,
+        location: Position,
+    },
+    BuildingLevelChanged {
+        name: String,
+        location: Position,
+        from: u32,
+        to: u32,
+    },
+    RequestOpened {
+        id: String,
+        name: String,
+    },
+    RequestClosed {
+        id: String,
+        name: String,
+    },
+    /// A request has been open for longer than the recorder's `stuck_after`. Only sent once per request.
+    RequestStuck {
+        id: String,
+        name: String,
+        open_for: Duration,
+    },
+}
+
+/// Appends colony snapshots to a JSON-lines file and reports what changed between them.
+#[derive(Debug, Clone)]
+pub struct ColonyRecorder {
+    path: PathBuf,
+    interval: Duration,
+    stuck_after: Duration,
+    previous: Option<ColonySnapshot>,
+    reported_stuck: HashSet<String>,
+    // set when the file ends in a line cut short (i.e. by a crash), so the
+    // next snapshot doesn't get appended to it
+    newline_first: bool,
+}
+
+impl ColonyRecorder {
+    /// Creates a recorder appending to `path`. The first snapshot recorded is compared
+    /// against the last one in the file, if there is one.
+    pub async fn new(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let (previous, newline_first) = match tokio::fs::try_exists(&path).await? {
+            true => load_last_snapshot(&path).await?,
+            false => (None, false),
+        };
+
+        Ok(Self {
+            path,
+            interval: Duration::from_secs(60),
+            stuck_after: Duration::from_secs(30 * 60),
+            previous,
+            reported_stuck: HashSet::new(),
+            newline_first,
+        })
+    }
+
+    /// How long [`ColonyRecorder::run`] waits between snapshots. Defaults to a minute.
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// How long a request can be open before it is reported as stuck. Defaults to 30 minutes.
+    pub fn stuck_after(self, stuck_after: Duration) -> Self {
+        Self {
+            stuck_after,
+            ..self
+        }
+    }
+
+    pub fn previous(&self) -> Option<&ColonySnapshot> {
+        self.previous.as_ref()
+    }
+
+    /// Takes a snapshot, appends it to the file and returns what changed since the last one.
+    pub async fn record(
+        &mut self,
+        colony: &ColonyIntegrator<'_>,
+    ) -> Result<(ColonySnapshot, Vec<ColonyEvent>)> {
+        let snapshot = self.snapshot(colony).await?;
+
+        let mut line = if self.newline_first {
+            "\n".to_string()
+        } else {
+            String::new()
+        };
+        line += &serde_json::to_string(&snapshot)?;
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        self.newline_first = false;
+
+        let events = self.diff(&snapshot);
+        self.previous = Some(snapshot.clone());
+
+        Ok((snapshot, events))
+    }
+
+    async fn snapshot(&self, colony: &ColonyIntegrator<'_>) -> Result<ColonySnapshot> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let first_seen = self
+            .previous
+            .iter()
+            .flat_map(|previous| &previous.requests)
+            .map(|request| (request.id.clone(), request.first_seen))
+            .collect::<HashMap<_, _>>();
+        let request_snapshot = |request: &ColonyRequest| RequestSnapshot {
+            id: request.id.clone(),
+            name: request.name.clone(),
+            count: request.count,
+            state: request.state.clone(),
+            first_seen: first_seen.get(&request.id).copied().unwrap_or(timestamp),
+        };
+
+        Ok(ColonySnapshot {
+            timestamp,
+            citizens: colony
+                .get_citizens()
+                .await?
+                .iter()
+                .map(Into::into)
+                .collect(),
+            visitors: colony
+                .get_visitors()
+                .await?
+                .iter()
+                .map(|visitor| (visitor.citizen.id.clone(), visitor.citizen.name.clone()))
+                .collect(),
+            buildings: colony
+                .get_buildings()
+                .await?
+                .iter()
+                .map(Into::into)
+                .collect(),
+            requests: colony
+                .get_requests()
+                .await?
+                .iter()
+                .map(request_snapshot)
+                .collect(),
+        })
+    }
+
+    fn diff(&mut self, current: &ColonySnapshot) -> Vec<ColonyEvent> {
+        let mut events = Vec::new();
+
+        for request in &current.requests {
+            let open_for =
+                Duration::from_secs(current.timestamp.saturating_sub(request.first_seen));
+            if open_for >= self.stuck_after && self.reported_stuck.insert(request.id.clone()) {
+                events.push(ColonyEvent::RequestStuck {
+                    id: request.id.clone(),
+                    name: request.name.clone(),
+                    open_for,
+                });
+            }
+        }
+        let open = current
+            .requests
+            .iter()
+            .map(|request| &request.id)
+            .collect::<HashSet<_>>();
+        self.reported_stuck.retain(|id| open.contains(id));
+
+        // the first snapshot is the baseline, everything in it would be "new"
+        let Some(previous) = &self.previous else {
+            return events;
+        };
+
+        let citizens = |snapshot: &ColonySnapshot| {
+            snapshot
+                .citizens
+                .iter()
+                .map(|c| (c.id.clone(), c.name.clone()))
+                .collect::<HashMap<_, _>>()
+        };
+        let (before, after) = (citizens(previous), citizens(current));
+        for (id, name) in &after {
+            if !before.contains_key(id) {
+                events.push(ColonyEvent::CitizenJoined {
+                    id: id.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+        for (id, name) in &before {
+            if !after.contains_key(id) {
+                events.push(ColonyEvent::CitizenLost {
+                    id: id.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        for (id, name) in &current.visitors {
+            if !previous
+                .visitors
+                .iter()
+                .any(|(previous_id, _)| previous_id == id)
+            {
+                events.push(ColonyEvent::NewVisitor {
+                    id: id.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        let buildings = |snapshot: &ColonySnapshot| {
+            snapshot
+                .buildings
+                .iter()
+                .map(|b| (b.key(), b.clone()))
+                .collect::<HashMap<_, _>>()
+        };
+        let (before, after) = (buildings(previous), buildings(current));
+        for (key, building) in &after {
+            match before.get(key) {
+                None => events.push(ColonyEvent::BuildingPlaced {
+                    name: building.name.clone(),
+                    location: building.location.clone(),
+                }),
+                Some(old) if old.level != building.level => {
+                    events.push(ColonyEvent::BuildingLevelChanged {
+                        name: building.name.clone(),
+                        location: building.location.clone(),
+                        from: old.level,
+                        to: building.level,
+                    })
+                }
+                _ => {}
+            }
+        }
+        for (key, building) in &before {
+            if !after.contains_key(key) {
+                events.push(ColonyEvent::BuildingRemoved {
+                    name: building.name.clone(),
+                    location: building.location.clone(),
+                });
+            }
+        }
+
+        let requests = |snapshot: &ColonySnapshot| {
+            snapshot
+                .requests
+                .iter()
+                .map(|r| (r.id.clone(), r.name.clone()))
+                .collect::<HashMap<_, _>>()
+        };
+        let (before, after) = (requests(previous), requests(current));
+        for (id, name) in &after {
+            if !before.contains_key(id) {
+                events.push(ColonyEvent::RequestOpened {
+                    id: id.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+        for (id, name) in &before {
+            if !after.contains_key(id) {
+                events.push(ColonyEvent::RequestClosed {
+                    id: id.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        events
+    }
+
+    /// Records a snapshot every [`ColonyRecorder::interval`], yielding the changes. The stream
+    /// ends after the first error (i.e. the computer disconnecting).
+    pub fn run<'n>(
+        self,
+        colony: &'n ColonyIntegrator<'n>,
+    ) -> impl Stream<Item = Result<ColonyEvent>> + Send + Unpin + 'n {
+        let state = Some((self, Vec::new().into_iter(), true));
+        Box::pin(futures_util::stream::unfold(
+            state,
+            move |state| async move {
+                let (mut recorder, mut pending, mut first) = state?;
+
+                loop {
+                    if let Some(event) = pending.next() {
+                        return Some((Ok(event), Some((recorder, pending, first))));
+                    }
+
+                    if !first {
+                        tokio::time::sleep(recorder.interval).await;
+                    }
+                    first = false;
+
+                    match recorder.record(colony).await {
+                        Ok((_, events)) => pending = events.into_iter(),
+                        Err(err) => return Some((Err(err), None)),
+                    }
+                }
+            },
+        ))
+    }
+}
+
+/// Reads every snapshot from a file written by a [`ColonyRecorder`], oldest first. Lines that
+/// can't be read (i.e. one cut short by a crash while it was being written) are skipped.
+pub async fn load_snapshots(path: impl AsRef<Path>) -> Result<Vec<ColonySnapshot>> {
+    let contents = tokio::fs::read(path).await?;
+
+    Ok(contents
+        .split(|&b| b == b'\n')
+        .filter_map(parse_snapshot)
+        .collect())
+}
+
+// reads only the end of the file, returning the last snapshot that can be read
+// and whether the file ends in an unfinished line
+async fn load_last_snapshot(path: &Path) -> Result<(Option<ColonySnapshot>, bool)> {
+    const BLOCK_SIZE: u64 = 64 * 1024;
+
+    let mut file = tokio::fs::File::open(path).await?;
+    let len = file.metadata().await?.len();
+    let mut end = len;
+    let mut unfinished = false;
+    // the start of the earliest line read so far, which may continue in the previous block
+    let mut partial = Vec::new();
+
+    while end > 0 {
+        let start = end.saturating_sub(BLOCK_SIZE);
+        let mut block = vec![0; (end - start) as usize];
+        file.seek(SeekFrom::Start(start)).await?;
+        file.read_exact(&mut block).await?;
+        if end == len {
+            unfinished = block.last() != Some(&b'\n');
+        }
+        block.append(&mut partial);
+        end = start;
+
+        let mut lines = block.split(|&b| b == b'\n').collect::<Vec<_>>();
+        if end > 0 {
+            partial = lines.remove(0).to_vec();
+        }
+        if let Some(snapshot) = lines.into_iter().rev().find_map(parse_snapshot) {
+            return Ok((Some(snapshot), unfinished));
+        }
+    }
+
+    Ok((None, unfinished))
+}
+
+fn parse_snapshot(line: &[u8]) -> Option<ColonySnapshot> {
+    if line.trim_ascii().is_empty() {
+        return None;
+    }
+
+    match serde_json::from_slice(line) {
+        Ok(snapshot) => Some(snapshot),
+        Err(err) => {
+            warn!("Skipping colony snapshot that can't be read: {}", err);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(timestamp: u64) -> String {
+        format!(
+            r#"{{"timestamp":{timestamp},"citizens":[],"visitors":[],"buildings":[],"requests":[]}}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn skips_truncated_last_line() {
+        let path = std::env::temp_dir().join(format!("colony-{}.jsonl", std::process::id()));
+        // enough lines to span several blocks
+        let mut contents = (1..=3000).map(|i| line(i) + "\n").collect::<String>();
+        contents += &line(3001)[..20];
+        tokio::fs::write(&path, contents).await.unwrap();
+
+        let (last, unfinished) = load_last_snapshot(&path).await.unwrap();
+        let snapshots = load_snapshots(&path).await.unwrap();
+        let recorder = ColonyRecorder::new(&path).await.unwrap();
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(last.map(|snapshot| snapshot.timestamp), Some(3000));
+        assert!(unfinished);
+        assert_eq!(snapshots.len(), 3000);
+        assert!(recorder.newline_first);
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    lua_compat::LuaVec,
+    wrappers::{prelude::*, shared::direction::Direction},
+};
+
+pub use super::shared::{filter::ItemFilter, fluid::Fluid, item::Item};
+mod crafting_cpu;
+pub use crafting_cpu::*;
+
+generate_wrapper_impl!(MeBridge = "meBridge");
+
+impl<'a> MeBridge<'a> {
+    pub async fn list_items(&self) -> Result<Vec<Item>> {
+        let items: LuaVec<Item> = self
+            .inner
+            .call_method_with("listItems", Value::Null)
+            .await?;
+
+        Ok(items.0)
+    }
+
+    /// Gets an item in the system, `None` if there is none of it stored and it can't be crafted.
+    pub async fn get_item(&self, item: ItemFilter) -> Result<Option<Item>> {
+        self.inner
+            .call_method_with_optional("getItem", vec![item])
+            .await
+    }
+
+    pub async fn list_craftable_items(&self) -> Result<Vec<Item>> {
+        let items: LuaVec<Item> = self
+            .inner
+            .call_method_with("listCraftableItems", Value::Null)
+            .await?;
+
+        Ok(items.0)
+    }
+
+    pub async fn is_item_craftable(&self, item: ItemFilter) -> Result<bool> {
+        self.inner
+            .call_method_with("isItemCraftable", vec![item])
+            .await
+    }
+
+    /// Starts crafting an item, on the named crafting CPU if `cpu` is given.
+    pub async fn craft_item(&self, item: ItemFilter, cpu: Option<&str>) -> Result<bool> {
+        self.inner
+            .call_method_with("craftItem", (item, cpu.map(str::to_string)))
+            .await
+    }
+
+    /// Returns true if the item is being crafted, only on the named crafting CPU if `cpu` is given.
+    pub async fn is_item_crafting(&self, item: ItemFilter, cpu: Option<&str>) -> Result<bool> {
+        self.inner
+            .call_method_with("isItemCrafting", (item, cpu.map(str::to_string)))
+            .await
+    }
+
+    pub async fn get_crafting_cpus(&self) -> Result<Vec<CraftingCpu>> {
+        let cpus: LuaVec<CraftingCpu> = self
+            .inner
+            .call_method_with("getCraftingCPUs", Value::Null)
+            .await?;
+
+        Ok(cpus.0)
+    }
+
+    /// Exports items into the container (on the same computer), returning the number exported.
+    pub async fn export_item_to_peripheral(
+        &self,
+        item: ItemFilter,
+        container: impl ToString,
+    ) -> Result<usize> {
+        self.inner
+            .call_method_with("exportItemToPeripheral", (item, container.to_string()))
+            .await
+    }
+
+    pub async fn import_item_from_peripheral(
+        &self,
+        item: ItemFilter,
+        container: impl ToString,
+    ) -> Result<usize> {
+        self.inner
+            .call_method_with("importItemFromPeripheral", (item, container.to_string()))
+            .await
+    }
+
+    /// Exports items into the container next to the bridge in `direction`, returning the number exported.
+    pub async fn export_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+        self.inner
+            .call_method_with("exportItem", (item, direction))
+            .await
+    }
+
+    pub async fn import_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+        self.inner
+            .call_method_with("importItem", (item, direction))
+            .await
+    }
+
+    pub async fn list_fluids(&self) -> Result<Vec<Fluid>> {
+        let fluids: LuaVec<Fluid> = self
+            .inner
+            .call_method_with("listFluid", Value::Null)
+            .await?;
+
+        Ok(fluids.0)
+    }
+
+    pub async fn list_craftable_fluids(&self) -> Result<Vec<Fluid>> {
+        let fluids: LuaVec<Fluid> = self
+            .inner
+            .call_method_with("listCraftableFluid", Value::Null)
+            .await?;
+
+        Ok(fluids.0)
+    }
+
+    pub async fn craft_fluid(&self, fluid: ItemFilter, cpu: Option<&str>) -> Result<bool> {
+        self.inner
+            .call_method_with("craftFluid", (fluid, cpu.map(str::to_string)))
+            .await
+    }
+
+    /// Exports fluid into the tank next to the bridge in `direction`, returning the
+    /// amount exported in millibuckets. The filter's count is in millibuckets.
+    pub async fn export_fluid(&self, fluid: ItemFilter, direction: Direction) -> Result<u64> {
+        self.inner
+            .call_method_with("exportFluid", (fluid, direction))
+            .await
+    }
+
+    pub async fn export_fluid_to_peripheral(
+        &self,
+        fluid: ItemFilter,
+        container: impl ToString,
+    ) -> Result<u64> {
+        self.inner
+            .call_method_with("exportFluidToPeripheral", (fluid, container.to_string()))
+            .await
+    }
+
+    pub async fn import_fluid(&self, fluid: ItemFilter, direction: Direction) -> Result<u64> {
+        self.inner
+            .call_method_with("importFluid", (fluid, direction))
+            .await
+    }
+
+    /// The energy stored in the system, in AE.
+    pub async fn get_energy_storage(&self) -> Result<f64> {
+        self.inner
+            .call_method_with("getEnergyStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_max_energy_storage(&self) -> Result<f64> {
+        self.inner
+            .call_method_with("getMaxEnergyStorage", Value::Null)
+            .await
+    }
+
+    /// The energy used by the system, in AE/t.
+    pub async fn get_energy_usage(&self) -> Result<f64> {
+        self.inner
+            .call_method_with("getEnergyUsage", Value::Null)
+            .await
+    }
+
+    /// The number of bytes of item storage in the system.
+    pub async fn get_total_item_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getTotalItemStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_used_item_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getUsedItemStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_available_item_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getAvailableItemStorage", Value::Null)
+            .await
+    }
+
+    /// The number of bytes of fluid storage in the system.
+    pub async fn get_total_fluid_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getTotalFluidStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_used_fluid_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getUsedFluidStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_available_fluid_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getAvailableFluidStorage", Value::Null)
+            .await
+    }
+}
@@ -0,0 +1,10 @@
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CraftingCpu {
+    /// Only present for CPUs that were named in an anvil.
+    pub name: Option<String>,
+    /// The CPU's storage, in bytes.
+    pub storage: u64,
+    pub co_processors: u32,
+    pub is_busy: bool,
+}
@@ -1,2 +1,10 @@
+pub mod chat_box;
 pub mod colony_integrator;
+pub mod colony_recorder;
+pub mod me_bridge;
+pub mod player_detector;
+pub mod request_fulfiller;
 pub mod rs_bridge;
+pub mod shared;
+pub mod stock_keeper;
+pub mod storage_network;
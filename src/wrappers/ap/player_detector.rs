@@ -0,0 +1,200 @@
+use futures_util::Stream;
+
+use crate::{
+    error::Error,
+    event::{Event, EventReceiver},
+    lua_compat::LuaVec,
+    wrappers::prelude::*,
+};
+
+mod geofence;
+pub use geofence::*;
+
+generate_wrapper_impl!(PlayerDetector = "playerDetector");
+
+/// A block position, used for the corners of the areas searched by [`PlayerDetector`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockPos {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl BlockPos {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+}
+
+/// A player's position from [`PlayerDetector::get_player_pos`]. Which fields other than the
+/// coordinates are present depends on the Advanced Peripherals version and config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerPos {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: Option<f64>,
+    pub pitch: Option<f64>,
+    /// i.e. `minecraft:overworld`
+    pub dimension: Option<String>,
+    pub eye_height: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerEvent {
+    /// A player right-clicked the detector with the given address.
+    Click {
+        username: String,
+        device: String,
+    },
+    Join {
+        username: String,
+        dimension: String,
+    },
+    Leave {
+        username: String,
+        dimension: String,
+    },
+    ChangedDimension {
+        username: String,
+        from: String,
+        to: String,
+    },
+}
+
+/// The events sent by player detectors.
+pub const PLAYER_EVENTS: [&str; 4] = [
+    "playerClick",
+    "playerJoin",
+    "playerLeave",
+    "playerChangedDimension",
+];
+
+impl PlayerEvent {
+    pub fn from_event(event: &Event) -> Result<Self> {
+        Ok(match event.name.as_str() {
+            "playerClick" => Self::Click {
+                username: event.param(0)?,
+                device: event.param(1)?,
+            },
+            "playerJoin" => Self::Join {
+                username: event.param(0)?,
+                dimension: event.param(1)?,
+            },
+            "playerLeave" => Self::Leave {
+                username: event.param(0)?,
+                dimension: event.param(1)?,
+            },
+            "playerChangedDimension" => Self::ChangedDimension {
+                username: event.param(0)?,
+                from: event.param(1)?,
+                to: event.param(2)?,
+            },
+            _ => {
+                return crate::debug_feature!(Err(Error::UnexpectedData(vec![event
+                    .name
+                    .clone()
+                    .into()])))
+            }
+        })
+    }
+
+    pub fn username(&self) -> &str {
+        match self {
+            Self::Click { username, .. }
+            | Self::Join { username, .. }
+            | Self::Leave { username, .. }
+            | Self::ChangedDimension { username, .. } => username,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PlayerEventReceiver {
+    events: EventReceiver,
+}
+
+impl PlayerEventReceiver {
+    pub async fn recv(&mut self) -> Result<PlayerEvent> {
+        PlayerEvent::from_event(&self.events.recv().await?)
+    }
+
+    pub fn into_stream(self) -> impl Stream<Item = PlayerEvent> + Send + Unpin {
+        Box::pin(futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.ok().map(|event| (event, rx))
+        }))
+    }
+}
+
+impl<'a> PlayerDetector<'a> {
+    pub async fn get_online_players(&self) -> Result<Vec<String>> {
+        self.names("getOnlinePlayers", Value::Null).await
+    }
+
+    /// The players within `range` blocks of the detector.
+    pub async fn get_players_in_range(&self, range: u32) -> Result<Vec<String>> {
+        self.names("getPlayersInRange", range).await
+    }
+
+    /// The players in the box between two corners, in world coordinates.
+    pub async fn get_players_in_coords(&self, from: BlockPos, to: BlockPos) -> Result<Vec<String>> {
+        self.names("getPlayersInCoords", (from, to)).await
+    }
+
+    /// The players in a box of the given size centered on the detector.
+    pub async fn get_players_in_cubic(
+        &self,
+        width: u32,
+        height: u32,
+        depth: u32,
+    ) -> Result<Vec<String>> {
+        self.names("getPlayersInCubic", (width, height, depth))
+            .await
+    }
+
+    pub async fn is_player_in_range(&self, range: u32, username: impl ToString) -> Result<bool> {
+        self.inner
+            .call_method_with("isPlayerInRange", (range, username.to_string()))
+            .await
+    }
+
+    /// Returns true if any player is within `range` blocks of the detector.
+    pub async fn is_players_in_range(&self, range: u32) -> Result<bool> {
+        self.inner.call_method_with("isPlayersInRange", range).await
+    }
+
+    /// The position of an online player, `None` if they can't be found (i.e. they are in
+    /// another dimension and the server doesn't allow that).
+    pub async fn get_player_pos(&self, username: impl ToString) -> Result<Option<PlayerPos>> {
+        let pos: Option<Value> = self
+            .inner
+            .call_method_with_optional("getPlayerPos", username.to_string())
+            .await?;
+
+        match pos {
+            // some versions return an empty table rather than nil for a player that isn't found
+            Some(Value::Object(pos)) if pos.is_empty() => Ok(None),
+            Some(Value::Array(pos)) if pos.is_empty() => Ok(None),
+            Some(pos) => Ok(Some(serde_json::from_value(pos)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Subscribes to every player detector event from now on.
+    pub async fn events(&self) -> Result<PlayerEventReceiver> {
+        Ok(PlayerEventReceiver {
+            events: self.inner.computer.subscribe_all(PLAYER_EVENTS).await?,
+        })
+    }
+
+    async fn names<S: crate::request::PeripheralArgs>(
+        &self,
+        method: &str,
+        args: S,
+    ) -> Result<Vec<String>> {
+        let names: LuaVec<String> = self.inner.call_method_with(method, args).await?;
+
+        Ok(names.0)
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+
+use futures_util::Stream;
+
+use crate::error::Result;
+
+use super::{BlockPos, PlayerDetector, PlayerEventReceiver, PlayerPos};
+
+/// A named box of blocks in the world, including the blocks at both corners. A player is in the
+/// region when they are standing anywhere inside one of its blocks (i.e. at x = 104.5 for a
+/// corner at x = 104).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+    pub name: String,
+    pub min: BlockPos,
+    pub max: BlockPos,
+    /// Only players in this dimension are in the region, any dimension if `None`.
+    pub dimension: Option<String>,
+}
+
+impl Region {
+    /// Creates a region between two corners, in any order.
+    pub fn new(name: impl ToString, a: BlockPos, b: BlockPos) -> Self {
+        Self {
+            name: name.to_string(),
+            min: BlockPos::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: BlockPos::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+            dimension: None,
+        }
+    }
+
+    pub fn in_dimension(self, dimension: impl ToString) -> Self {
+        Self {
+            dimension: Some(dimension.to_string()),
+            ..self
+        }
+    }
+
+    pub fn contains(&self, pos: &PlayerPos) -> bool {
+        // positions without a dimension are assumed to be in the region's
+        let in_dimension = match (&self.dimension, &pos.dimension) {
+            (Some(dimension), Some(pos_dimension)) => dimension == pos_dimension,
+            _ => true,
+        };
+
+        // the far edge of the max corner's block is one past its coordinate
+        let within = |min: i64, max: i64, pos: f64| pos >= min as f64 && pos < (max + 1) as f64;
+
+        in_dimension
+            && within(self.min.x, self.max.x, pos.x)
+            && within(self.min.y, self.max.y, pos.y)
+            && within(self.min.z, self.max.z, pos.z)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeofenceEvent {
+    Enter { username: String, region: String },
+    Leave { username: String, region: String },
+}
+
+/// Tracks which players are in which [`Region`]s with a player detector.
+///
+/// Positions are polled every [`Geofence::interval`], and right away when a player detector
+/// event (i.e. a player joining or changing dimension) is received.
+#[derive(Debug)]
+pub struct Geofence<'d, 'a> {
+    detector: &'d PlayerDetector<'a>,
+    regions: Vec<Region>,
+    interval: Duration,
+    // username -> names of the regions they were in at the last poll
+    inside: HashMap<String, BTreeSet<String>>,
+}
+
+impl<'d, 'a> Geofence<'d, 'a> {
+    pub fn new(detector: &'d PlayerDetector<'a>) -> Self {
+        Self {
+            detector,
+            regions: Vec::new(),
+            interval: Duration::from_secs(1),
+            inside: HashMap::new(),
+        }
+    }
+
+    pub fn with_region(mut self, region: Region) -> Self {
+        self.regions.push(region);
+        self
+    }
+
+    /// How often positions are polled. Defaults to a second.
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// The players in the region with the given name, as of the last poll.
+    pub fn players_in(&self, region: &str) -> Vec<&str> {
+        self.inside
+            .iter()
+            .filter(|(_, regions)| regions.contains(region))
+            .map(|(username, _)| username.as_str())
+            .collect()
+    }
+
+    /// Gets every online player's position and returns who entered or left a region since the
+    /// last poll. Players that went offline leave every region they were in.
+    pub async fn poll(&mut self) -> Result<Vec<GeofenceEvent>> {
+        let mut now_inside = HashMap::new();
+        for username in self.detector.get_online_players().await? {
+            let regions = match self.detector.get_player_pos(&username).await? {
+                Some(pos) => self
+                    .regions
+                    .iter()
+                    .filter(|region| region.contains(&pos))
+                    .map(|region| region.name.clone())
+                    .collect(),
+                None => BTreeSet::new(),
+            };
+            if !regions.is_empty() {
+                now_inside.insert(username, regions);
+            }
+        }
+
+        let mut events = Vec::new();
+        let empty = BTreeSet::new();
+        for (username, regions) in &now_inside {
+            let before = self.inside.get(username).unwrap_or(&empty);
+            for region in regions.difference(before) {
+                events.push(GeofenceEvent::Enter {
+                    username: username.clone(),
+                    region: region.clone(),
+                });
+            }
+        }
+        for (username, regions) in &self.inside {
+            let after = now_inside.get(username).unwrap_or(&empty);
+            for region in regions.difference(after) {
+                events.push(GeofenceEvent::Leave {
+                    username: username.clone(),
+                    region: region.clone(),
+                });
+            }
+        }
+
+        self.inside = now_inside;
+
+        Ok(events)
+    }
+
+    /// Polls until the first error (i.e. the computer disconnecting), yielding every change.
+    pub fn run(self) -> impl Stream<Item = Result<GeofenceEvent>> + Send + Unpin + 'd
+    where
+        'a: 'd,
+    {
+        let state = Some((self, None::<PlayerEventReceiver>, Vec::new().into_iter()));
+        Box::pin(futures_util::stream::unfold(state, |state| async move {
+            let (mut geofence, mut player_events, mut pending) = state?;
+
+            loop {
+                if let Some(event) = pending.next() {
+                    return Some((Ok(event), Some((geofence, player_events, pending))));
+                }
+
+                // the first poll happens right away
+                match &mut player_events {
+                    None => match geofence.detector.events().await {
+                        Ok(events) => player_events = Some(events),
+                        Err(err) => return Some((Err(err), None)),
+                    },
+                    Some(events) => {
+                        tokio::select! {
+                            _ = tokio::time::sleep(geofence.interval) => {}
+                            event = events.recv() => {
+                                if let Err(err) = event {
+                                    return Some((Err(err), None));
+                                }
+                            }
+                        }
+                    }
+                }
+
+                match geofence.poll().await {
+                    Ok(events) => pending = events.into_iter(),
+                    Err(err) => return Some((Err(err), None)),
+                }
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: f64, y: f64, z: f64) -> PlayerPos {
+        PlayerPos {
+            x,
+            y,
+            z,
+            yaw: None,
+            pitch: None,
+            dimension: Some("minecraft:overworld".to_string()),
+            eye_height: None,
+        }
+    }
+
+    #[test]
+    fn contains_whole_corner_blocks() {
+        let region = Region::new(
+            "door",
+            BlockPos::new(104, 68, 204),
+            BlockPos::new(100, 64, 200),
+        );
+
+        assert!(region.contains(&pos(100.0, 64.0, 200.0)));
+        assert!(region.contains(&pos(104.5, 68.9, 204.99)));
+        assert!(!region.contains(&pos(105.0, 64.0, 200.0)));
+        assert!(!region.contains(&pos(99.9, 64.0, 200.0)));
+        assert!(!region
+            .in_dimension("minecraft:the_nether")
+            .contains(&pos(102.0, 66.0, 202.0)));
+    }
+}
@@ -0,0 +1,333 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use futures_util::Stream;
+use serde_json::Value;
+
+use crate::{error::Result, wrappers::shared::direction::Direction};
+
+use super::{
+    colony_integrator::{ColonyIntegrator, ColonyRequest, RequestedItem},
+    shared::{filter::ItemFilter, item::Item, nbt},
+    storage_network::StorageNetwork,
+};
+
+/// Where requested items are exported to, usually an inventory the warehouse's couriers pick up from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// A container attached to the same computer as the bridge.
+    Peripheral(String),
+    /// The container next to the bridge.
+    Direction(Direction),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestOutcome {
+    /// Everything requested was exported.
+    Fulfilled,
+    /// Some of the request could not be exported, and crafting wasn't attempted.
+    Partial { missing: usize },
+    /// A craft was started for the items that couldn't be exported.
+    Crafting { item: String, missing: usize },
+    /// Nothing could be exported or crafted for the request.
+    Unfulfillable { missing: usize },
+    /// Everything requested was exported by an earlier pass, and the colony hasn't collected it yet.
+    AlreadyExported,
+}
+
+#[derive(Debug, Clone)]
+pub struct RequestResult {
+    pub request: ColonyRequest,
+    /// The items exported (or that would be, in a dry run) by this pass, by name.
+    pub exported: Vec<(String, usize)>,
+    pub outcome: RequestOutcome,
+}
+
+#[derive(Debug, Clone)]
+pub struct FulfilmentReport {
+    pub dry_run: bool,
+    pub results: Vec<RequestResult>,
+}
+
+impl FulfilmentReport {
+    /// The requests that could not be exported or crafted.
+    pub fn unfulfillable(&self) -> impl Iterator<Item = &RequestResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, RequestOutcome::Unfulfillable { .. }))
+    }
+}
+
+/// Delivers items requested by a MineColonies colony from an RS or ME system.
+///
+/// Exported amounts are remembered per request, so a request is not exported again while the
+/// colony is still collecting it.
+#[derive(Debug, Clone)]
+pub struct RequestFulfiller {
+    target: ExportTarget,
+    craft_missing: bool,
+    dry_run: bool,
+    interval: Duration,
+    // request ID -> items exported for it so far
+    exported: HashMap<String, usize>,
+}
+
+impl RequestFulfiller {
+    pub fn new(target: ExportTarget) -> Self {
+        Self {
+            target,
+            craft_missing: false,
+            dry_run: false,
+            interval: Duration::from_secs(10),
+            exported: HashMap::new(),
+        }
+    }
+
+    /// Starts crafts for requested items that aren't stored. Defaults to false.
+    pub fn craft_missing(self, craft_missing: bool) -> Self {
+        Self {
+            craft_missing,
+            ..self
+        }
+    }
+
+    /// Works out what would be exported and crafted without doing it. Defaults to false.
+    pub fn dry_run(self, dry_run: bool) -> Self {
+        Self { dry_run, ..self }
+    }
+
+    /// How long [`RequestFulfiller::run`] waits between passes. Defaults to 10 seconds.
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    /// Goes through every open request once.
+    pub async fn fulfil<N: StorageNetwork + ?Sized>(
+        &mut self,
+        colony: &ColonyIntegrator<'_>,
+        network: &N,
+    ) -> Result<FulfilmentReport> {
+        let requests = colony.get_requests().await?.0;
+        let mut stock = network.list_items().await?;
+
+        // forget requests the colony has completed or cancelled
+        let open = requests
+            .iter()
+            .map(|request| request.id.clone())
+            .collect::<HashSet<_>>();
+        self.exported.retain(|id, _| open.contains(id));
+
+        let mut results = Vec::new();
+        for request in requests {
+            let result = self.fulfil_request(request, network, &mut stock).await?;
+            results.push(result);
+        }
+
+        Ok(FulfilmentReport {
+            dry_run: self.dry_run,
+            results,
+        })
+    }
+
+    async fn fulfil_request<N: StorageNetwork + ?Sized>(
+        &mut self,
+        request: ColonyRequest,
+        network: &N,
+        stock: &mut [Item],
+    ) -> Result<RequestResult> {
+        let already = self.exported.get(&request.id).copied().unwrap_or(0);
+        let mut remaining = request.count.saturating_sub(already);
+        if remaining == 0 {
+            return Ok(RequestResult {
+                request,
+                exported: Vec::new(),
+                outcome: RequestOutcome::AlreadyExported,
+            });
+        }
+
+        let mut exported = Vec::new();
+        for i in Self::matching_stock(&request, stock) {
+            if remaining == 0 {
+                break;
+            }
+
+            let stored = &mut stock[i];
+            let amount = remaining.min(stored.amount);
+            let moved = if self.dry_run {
+                amount
+            } else {
+                self.export(network, stored, amount).await?
+            };
+            if moved == 0 {
+                continue;
+            }
+
+            stored.amount -= moved;
+            remaining -= moved;
+            exported.push((stored.name.clone(), moved));
+        }
+
+        let total = exported.iter().map(|(_, amount)| amount).sum::<usize>();
+        if !self.dry_run && total > 0 {
+            *self.exported.entry(request.id.clone()).or_default() += total;
+        }
+
+        let outcome = if remaining == 0 {
+            RequestOutcome::Fulfilled
+        } else if let Some(item) = self.craft(&request, network, remaining).await? {
+            RequestOutcome::Crafting {
+                item,
+                missing: remaining,
+            }
+        } else if total > 0 {
+            RequestOutcome::Partial { missing: remaining }
+        } else {
+            RequestOutcome::Unfulfillable { missing: remaining }
+        };
+
+        Ok(RequestResult {
+            request,
+            exported,
+            outcome,
+        })
+    }
+
+    async fn export<N: StorageNetwork + ?Sized>(
+        &self,
+        network: &N,
+        stored: &Item,
+        amount: usize,
+    ) -> Result<usize> {
+        // the fingerprint pins the export to the variant that was matched, not just its name
+        let mut filter = ItemFilter::from_name(&stored.name).with_count(amount);
+        if let Some(fingerprint) = &stored.fingerprint {
+            filter = filter.with_fingerprint(fingerprint);
+        }
+        match &self.target {
+            ExportTarget::Peripheral(container) => {
+                network
+                    .export_item_to_peripheral(filter, container.clone())
+                    .await
+            }
+            ExportTarget::Direction(direction) => network.export_item(filter, *direction).await,
+        }
+    }
+
+    // starts crafting the first requested item that can be crafted, returning its name
+    async fn craft<N: StorageNetwork + ?Sized>(
+        &self,
+        request: &ColonyRequest,
+        network: &N,
+        amount: usize,
+    ) -> Result<Option<String>> {
+        if !self.craft_missing {
+            return Ok(None);
+        }
+
+        for item in request.items.iter() {
+            let filter = ItemFilter::from_name(&item.name);
+            if network.is_item_crafting(filter.clone()).await? {
+                return Ok(Some(item.name.clone()));
+            }
+            if self.dry_run {
+                let craftable = network
+                    .get_item(filter)
+                    .await?
+                    .is_some_and(|stored| stored.is_craftable);
+                if craftable {
+                    return Ok(Some(item.name.clone()));
+                }
+            } else if network.craft_item(filter.with_count(amount)).await? {
+                return Ok(Some(item.name.clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Indices into `stock` of items that satisfy the request, best matches first: the requested
+    /// items themselves in the order they were listed, then anything sharing every tag the
+    /// requested items have in common (i.e. any kind of planks for a request for planks).
+    fn matching_stock(request: &ColonyRequest, stock: &[Item]) -> Vec<usize> {
+        let mut matches = Vec::new();
+        for requested in request.items.iter() {
+            for (i, stored) in stock.iter().enumerate() {
+                if stored.amount > 0 && Self::same_item(requested, stored) && !matches.contains(&i)
+                {
+                    matches.push(i);
+                }
+            }
+        }
+
+        let common_tags = Self::common_tags(&request.items);
+        if !common_tags.is_empty() {
+            for (i, stored) in stock.iter().enumerate() {
+                let has_tags = stored
+                    .tags
+                    .as_ref()
+                    .is_some_and(|tags| common_tags.iter().all(|tag| tags.contains(tag)));
+                if stored.amount > 0 && has_tags && !matches.contains(&i) {
+                    matches.push(i);
+                }
+            }
+        }
+
+        matches
+    }
+
+    fn same_item(requested: &RequestedItem, stored: &Item) -> bool {
+        if requested.name != stored.name {
+            return false;
+        }
+
+        match &requested.nbt {
+            Value::Null => true,
+            Value::Object(nbt) if nbt.is_empty() => true,
+            requested => stored
+                .nbt
+                .as_ref()
+                .is_some_and(|stored| nbt::nbt_eq(requested, stored)),
+        }
+    }
+
+    // tags are only compared for requests that accept several items, a request for
+    // one item shouldn't be satisfied by everything that shares a tag with it
+    fn common_tags(items: &[RequestedItem]) -> Vec<String> {
+        let Some((first, rest)) = items.split_first().filter(|(_, rest)| !rest.is_empty()) else {
+            return Vec::new();
+        };
+
+        first
+            .tags
+            .iter()
+            .filter(|tag| rest.iter().all(|item| item.tags.contains(tag)))
+            .cloned()
+            .collect()
+    }
+
+    /// Runs a pass every [`RequestFulfiller::interval`], yielding a report for each. The stream
+    /// ends after the first error (i.e. the computer disconnecting).
+    pub fn run<'n, N: StorageNetwork + ?Sized>(
+        self,
+        colony: &'n ColonyIntegrator<'n>,
+        network: &'n N,
+    ) -> impl Stream<Item = Result<FulfilmentReport>> + Send + Unpin + 'n {
+        let state = Some((self, true));
+        Box::pin(futures_util::stream::unfold(
+            state,
+            move |state| async move {
+                let (mut fulfiller, first) = state?;
+                if !first {
+                    tokio::time::sleep(fulfiller.interval).await;
+                }
+
+                match fulfiller.fulfil(colony, network).await {
+                    Ok(report) => Some((Ok(report), Some((fulfiller, false)))),
+                    Err(err) => Some((Err(err), None)),
+                }
+            },
+        ))
+    }
+}
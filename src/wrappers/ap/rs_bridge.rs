@@ -1,46 +1,185 @@
-use crate::{error::Error, wrappers::prelude::*};
+use crate::{
+    lua_compat::LuaVec,
+    wrappers::{prelude::*, shared::direction::Direction},
+};
 
-mod item;
-pub use item::*;
-mod filter;
-pub use filter::*;
+pub use super::shared::{filter::ItemFilter, fluid::Fluid, item::Item};
 mod pattern;
 pub use pattern::*;
+mod craft_planner;
+pub use craft_planner::*;
+
+/// Kept for code written before the filter was shared with the ME bridge.
+pub type RsFilter = ItemFilter;
 
 generate_wrapper_impl!(RsBridge = "rsBridge");
 
 impl<'a> RsBridge<'a> {
     pub async fn list_items(&self) -> Result<Vec<Item>> {
-        self.inner.call_method_with("listItems", Value::Null).await
+        let items: LuaVec<Item> = self
+            .inner
+            .call_method_with("listItems", Value::Null)
+            .await?;
+
+        Ok(items.0)
+    }
+
+    /// Gets an item in the system, `None` if there is none of it stored and it can't be crafted.
+    pub async fn get_item(&self, item: ItemFilter) -> Result<Option<Item>> {
+        self.inner
+            .call_method_with_optional("getItem", vec![item])
+            .await
     }
 
-    pub async fn get_pattern(&self, item: RsFilter) -> Result<Option<Pattern>> {
-        match self.inner.call_method_with("getPattern", vec![item]).await {
-            Ok(v) => Ok(Some(v)),
-            #[cfg(not(feature = "debug"))]
-            Err(Error::NoReturnValues) => Ok(None),
-            #[cfg(not(feature = "debug"))]
-            Err(err) => Err(err),
-            #[cfg(feature = "debug")]
-            Err(err) => match err.downcast_ref::<Error>().unwrap() {
-                Error::NoReturnValues => Ok(None),
-                _ => Err(err),
-            },
-        }
+    pub async fn list_craftable_items(&self) -> Result<Vec<Item>> {
+        let items: LuaVec<Item> = self
+            .inner
+            .call_method_with("listCraftableItems", Value::Null)
+            .await?;
+
+        Ok(items.0)
+    }
+
+    pub async fn get_pattern(&self, item: ItemFilter) -> Result<Option<Pattern>> {
+        self.inner
+            .call_method_with_optional("getPattern", vec![item])
+            .await
+    }
+
+    pub async fn list_patterns(&self) -> Result<Vec<Pattern>> {
+        let patterns: LuaVec<Pattern> = self
+            .inner
+            .call_method_with("listPatterns", Value::Null)
+            .await?;
+
+        Ok(patterns.0)
     }
 
     generate_wrapped_fn!(
-        craft_item -> bool = |item: RsFilter| => craftItem(vec![item]);
+        craft_item -> bool = |item: ItemFilter| => craftItem(vec![item]);
         [Value::Bool(b)] => Ok(*b)
     );
 
     generate_wrapped_fn!(
-        is_item_crafting -> bool = |item: RsFilter| => isItemCrafting(vec![item]);
+        is_item_crafting -> bool = |item: ItemFilter| => isItemCrafting(vec![item]);
         [Value::Bool(b)] => Ok(*b)
     );
 
     generate_wrapped_fn!(
-        export_item_to_peripheral -> usize = |item: RsFilter, container: impl ToString| => exportItemToPeripheral((item, container.to_string()));
+        export_item_to_peripheral -> usize = |item: ItemFilter, container: impl ToString| => exportItemToPeripheral((item, container.to_string()));
         [Value::Number(n)] => Ok(n.as_u64().unwrap() as usize)
     );
+
+    /// Imports items from the container (on the same computer) into the system, returning the number imported.
+    pub async fn import_item_from_peripheral(
+        &self,
+        item: ItemFilter,
+        container: impl ToString,
+    ) -> Result<usize> {
+        self.inner
+            .call_method_with("importItemFromPeripheral", (item, container.to_string()))
+            .await
+    }
+
+    /// Exports items into the container next to the bridge in `direction`, returning the number exported.
+    pub async fn export_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+        self.inner
+            .call_method_with("exportItem", (item, direction))
+            .await
+    }
+
+    /// Imports items from the container next to the bridge in `direction`, returning the number imported.
+    pub async fn import_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+        self.inner
+            .call_method_with("importItem", (item, direction))
+            .await
+    }
+
+    pub async fn list_fluids(&self) -> Result<Vec<Fluid>> {
+        let fluids: LuaVec<Fluid> = self
+            .inner
+            .call_method_with("listFluids", Value::Null)
+            .await?;
+
+        Ok(fluids.0)
+    }
+
+    pub async fn list_craftable_fluids(&self) -> Result<Vec<Fluid>> {
+        let fluids: LuaVec<Fluid> = self
+            .inner
+            .call_method_with("listCraftableFluid", Value::Null)
+            .await?;
+
+        Ok(fluids.0)
+    }
+
+    /// Exports fluid into the tank next to the bridge in `direction`, returning the
+    /// amount exported in millibuckets. The filter's count is in millibuckets.
+    pub async fn export_fluid(&self, fluid: ItemFilter, direction: Direction) -> Result<u64> {
+        self.inner
+            .call_method_with("exportFluid", (fluid, direction))
+            .await
+    }
+
+    pub async fn export_fluid_to_peripheral(
+        &self,
+        fluid: ItemFilter,
+        container: impl ToString,
+    ) -> Result<u64> {
+        self.inner
+            .call_method_with("exportFluidToPeripheral", (fluid, container.to_string()))
+            .await
+    }
+
+    generate_wrapped_fn!(
+        craft_fluid -> bool = |fluid: ItemFilter| => craftFluid(vec![fluid]);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    /// The energy stored in the system, in FE.
+    pub async fn get_energy_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getEnergyStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_max_energy_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getMaxEnergyStorage", Value::Null)
+            .await
+    }
+
+    /// The energy used by the system, in FE/t.
+    pub async fn get_energy_usage(&self) -> Result<f64> {
+        self.inner
+            .call_method_with("getEnergyUsage", Value::Null)
+            .await
+    }
+
+    /// The number of items that can be stored on disks in the system.
+    pub async fn get_max_item_disk_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getMaxItemDiskStorage", Value::Null)
+            .await
+    }
+
+    /// The amount of fluid, in millibuckets, that can be stored on disks in the system.
+    pub async fn get_max_fluid_disk_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getMaxFluidDiskStorage", Value::Null)
+            .await
+    }
+
+    /// The number of items that can be stored in external storage connected to the system.
+    pub async fn get_max_item_external_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getMaxItemExternalStorage", Value::Null)
+            .await
+    }
+
+    pub async fn get_max_fluid_external_storage(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getMaxFluidExternalStorage", Value::Null)
+            .await
+    }
 }
@@ -0,0 +1,447 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt::{self, Write},
+};
+
+use crate::error::Result;
+
+use super::{ItemFilter, Pattern, RsBridge};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanStatus {
+    /// Everything needed is already stored.
+    InStock,
+    /// The shortfall is crafted with a pattern.
+    Craft,
+    /// There is not enough stored and there is no pattern for the item.
+    MissingPattern,
+    /// The item is needed (directly or not) to craft itself.
+    Cycle,
+}
+
+/// One item in a [`CraftPlan`], with the items needed to craft it as its children.
+#[derive(Debug, Clone)]
+pub struct PlanNode {
+    pub item: String,
+    pub needed: usize,
+    /// The amount taken from storage, including leftovers of earlier crafts in the plan.
+    pub from_stock: usize,
+    /// The number of times the pattern is run.
+    pub crafts: usize,
+    /// The amount that can be neither taken from storage nor crafted.
+    pub missing: usize,
+    pub status: PlanStatus,
+    pub children: Vec<PlanNode>,
+}
+
+impl PlanNode {
+    fn walk<'n>(&'n self, f: &mut impl FnMut(&'n PlanNode)) {
+        f(self);
+        for child in &self.children {
+            child.walk(f);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CraftPlan {
+    pub root: PlanNode,
+}
+
+impl CraftPlan {
+    /// Returns true if the plan can be carried out with what is stored right now.
+    pub fn is_craftable(&self) -> bool {
+        let mut craftable = true;
+        self.root.walk(&mut |node| craftable &= node.missing == 0);
+        craftable
+    }
+
+    /// The total amount of every item that can't be stored or crafted.
+    pub fn missing(&self) -> BTreeMap<String, usize> {
+        let mut missing = BTreeMap::new();
+        self.root.walk(&mut |node| {
+            if node.missing > 0 {
+                *missing.entry(node.item.clone()).or_default() += node.missing;
+            }
+        });
+        missing
+    }
+
+    /// The total amount of every item taken from storage, which is everything the craft consumes.
+    pub fn raw_materials(&self) -> BTreeMap<String, usize> {
+        let mut materials = BTreeMap::new();
+        self.root.walk(&mut |node| {
+            if node.from_stock > 0 {
+                *materials.entry(node.item.clone()).or_default() += node.from_stock;
+            }
+        });
+        materials
+    }
+
+    /// The items that are needed to craft themselves.
+    pub fn cycles(&self) -> Vec<String> {
+        let mut cycles = Vec::new();
+        self.root.walk(&mut |node| {
+            if node.status == PlanStatus::Cycle && !cycles.contains(&node.item) {
+                cycles.push(node.item.clone());
+            }
+        });
+        cycles
+    }
+
+    /// Renders the plan as an indented tree, one item per line.
+    pub fn render_tree(&self) -> String {
+        let mut out = String::new();
+        Self::render_node(&self.root, 0, &mut out);
+        out
+    }
+
+    fn render_node(node: &PlanNode, depth: usize, out: &mut String) {
+        let _ = write!(out, "{}{} x{}", "  ".repeat(depth), node.item, node.needed);
+        if node.from_stock > 0 {
+            let _ = write!(out, ", {} stored", node.from_stock);
+        }
+        if node.crafts > 0 {
+            let _ = write!(out, ", craft {} times", node.crafts);
+        }
+        match node.status {
+            PlanStatus::MissingPattern => {
+                let _ = write!(out, ", MISSING {} (no pattern)", node.missing);
+            }
+            PlanStatus::Cycle => {
+                let _ = write!(out, ", MISSING {} (cycle)", node.missing);
+            }
+            _ => {}
+        }
+        out.push('\n');
+
+        for child in &node.children {
+            Self::render_node(child, depth + 1, out);
+        }
+    }
+
+    /// Renders the plan as a Graphviz DOT graph, with missing items in red.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph craft_plan {\n    node [shape=box];\n");
+        let mut next_id = 0;
+        Self::dot_node(&self.root, &mut next_id, &mut out);
+        out.push_str("}\n");
+        out
+    }
+
+    fn dot_node(node: &PlanNode, next_id: &mut usize, out: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+
+        let mut label = format!("{} x{}", node.item, node.needed);
+        if node.from_stock > 0 {
+            let _ = write!(label, "\\n{} stored", node.from_stock);
+        }
+        if node.crafts > 0 {
+            let _ = write!(label, "\\ncraft {} times", node.crafts);
+        }
+        if node.missing > 0 {
+            let _ = write!(label, "\\nmissing {}", node.missing);
+        }
+        let color = if node.missing > 0 { "red" } else { "black" };
+        let _ = writeln!(
+            out,
+            "    n{id} [label=\"{}\", color={color}];",
+            label.replace('"', "\\\"")
+        );
+
+        for child in &node.children {
+            let child_id = Self::dot_node(child, next_id, out);
+            let _ = writeln!(out, "    n{id} -> n{child_id};");
+        }
+
+        id
+    }
+}
+
+impl fmt::Display for CraftPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render_tree())
+    }
+}
+
+/// Works out everything needed to craft an item from the patterns in an RS system, without
+/// starting any crafts.
+///
+/// When a pattern slot accepts several items, the first one is used.
+#[derive(Debug)]
+pub struct CraftPlanner<'b, 'a> {
+    bridge: &'b RsBridge<'a>,
+    patterns: HashMap<String, Option<Pattern>>,
+}
+
+impl<'b, 'a> CraftPlanner<'b, 'a> {
+    pub fn new(bridge: &'b RsBridge<'a>) -> Self {
+        Self {
+            bridge,
+            patterns: HashMap::new(),
+        }
+    }
+
+    /// Plans crafting `amount` of `item` against what is stored right now. Patterns are
+    /// cached, so planning several items with one planner only fetches each pattern once.
+    pub async fn plan(&mut self, item: impl ToString, amount: usize) -> Result<CraftPlan> {
+        let item = item.to_string();
+        self.fetch_patterns(&item).await?;
+
+        let mut stock = HashMap::new();
+        for stored in self.bridge.list_items().await? {
+            *stock.entry(stored.name).or_default() += stored.amount;
+        }
+
+        let root = Self::resolve(&self.patterns, &item, amount, &mut Vec::new(), &mut stock);
+
+        Ok(CraftPlan { root })
+    }
+
+    async fn fetch_patterns(&mut self, item: &str) -> Result<()> {
+        let mut queue = VecDeque::from([item.to_string()]);
+        let mut seen = HashSet::new();
+
+        while let Some(item) = queue.pop_front() {
+            if !seen.insert(item.clone()) {
+                continue;
+            }
+
+            if !self.patterns.contains_key(&item) {
+                let pattern = self
+                    .bridge
+                    .get_pattern(ItemFilter::from_name(&item))
+                    .await?;
+                self.patterns.insert(item.clone(), pattern);
+            }
+
+            if let Some(pattern) = &self.patterns[&item] {
+                queue.extend(Self::inputs(pattern).into_keys());
+            }
+        }
+
+        Ok(())
+    }
+
+    // the amount of each item a single run of the pattern consumes
+    fn inputs(pattern: &Pattern) -> BTreeMap<String, usize> {
+        let mut inputs = BTreeMap::new();
+        for slot in &pattern.inputs {
+            if let Some(item) = slot.items().and_then(|items| items.first()) {
+                *inputs.entry(item.name.clone()).or_default() += item.amount.max(1);
+            }
+        }
+        inputs
+    }
+
+    // kept apart from the bridge so it only depends on the patterns and stock it is given
+    fn resolve(
+        patterns: &HashMap<String, Option<Pattern>>,
+        item: &str,
+        needed: usize,
+        path: &mut Vec<String>,
+        stock: &mut HashMap<String, usize>,
+    ) -> PlanNode {
+        let stored = stock.entry(item.to_string()).or_default();
+        let from_stock = needed.min(*stored);
+        *stored -= from_stock;
+        let remaining = needed - from_stock;
+
+        let mut node = PlanNode {
+            item: item.to_string(),
+            needed,
+            from_stock,
+            crafts: 0,
+            missing: 0,
+            status: PlanStatus::InStock,
+            children: Vec::new(),
+        };
+        if remaining == 0 {
+            return node;
+        }
+
+        if path.iter().any(|parent| parent == item) {
+            node.missing = remaining;
+            node.status = PlanStatus::Cycle;
+            return node;
+        }
+
+        let Some(Some(pattern)) = patterns.get(item) else {
+            node.missing = remaining;
+            node.status = PlanStatus::MissingPattern;
+            return node;
+        };
+
+        let per_craft = pattern
+            .outputs
+            .iter()
+            .filter(|output| output.name == item)
+            .map(|output| output.amount)
+            .sum::<usize>()
+            .max(1);
+        let crafts = remaining.div_ceil(per_craft);
+        node.crafts = crafts;
+        node.status = PlanStatus::Craft;
+
+        path.push(item.to_string());
+        for (input, amount) in Self::inputs(pattern) {
+            node.children.push(Self::resolve(
+                patterns,
+                &input,
+                amount * crafts,
+                path,
+                stock,
+            ));
+        }
+        path.pop();
+
+        // leftovers can be used by the rest of the plan
+        *stock.entry(item.to_string()).or_default() += crafts * per_craft - remaining;
+        for byproduct in &pattern.byproducts {
+            if byproduct.amount > 0 && byproduct.name != "minecraft:air" {
+                *stock.entry(byproduct.name.clone()).or_default() += byproduct.amount * crafts;
+            }
+        }
+
+        node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn item(name: &str, amount: usize) -> Value {
+        json!({ "name": name, "amount": amount, "displayName": name })
+    }
+
+    fn pattern(
+        inputs: &[(&str, usize)],
+        output: (&str, usize),
+        byproduct: Option<(&str, usize)>,
+    ) -> Option<Pattern> {
+        let mut byproducts = vec![item("minecraft:air", 0); 9];
+        if let Some((name, amount)) = byproduct {
+            byproducts[0] = item(name, amount);
+        }
+
+        Some(
+            serde_json::from_value(json!({
+                "inputs": inputs
+                    .iter()
+                    .map(|(name, amount)| vec![item(name, *amount)])
+                    .collect::<Vec<_>>(),
+                "outputs": [item(output.0, output.1)],
+                "byproducts": byproducts,
+                "processing": false,
+            }))
+            .unwrap(),
+        )
+    }
+
+    fn resolve(
+        patterns: &[(&str, Option<Pattern>)],
+        stock: &[(&str, usize)],
+        item: &str,
+        amount: usize,
+    ) -> PlanNode {
+        let patterns = patterns
+            .iter()
+            .map(|(name, pattern)| (name.to_string(), pattern.clone()))
+            .collect();
+        let mut stock = stock
+            .iter()
+            .map(|(name, amount)| (name.to_string(), *amount))
+            .collect();
+
+        CraftPlanner::resolve(&patterns, item, amount, &mut Vec::new(), &mut stock)
+    }
+
+    #[test]
+    fn rounds_crafts_up() {
+        // 4 planks per log, so the 10 planks not in stock take 3 crafts and 3 logs
+        let root = resolve(
+            &[
+                ("planks", pattern(&[("log", 1)], ("planks", 4), None)),
+                ("log", None),
+            ],
+            &[("planks", 1), ("log", 64)],
+            "planks",
+            11,
+        );
+
+        assert_eq!(root.from_stock, 1);
+        assert_eq!(root.crafts, 3);
+        assert_eq!(root.status, PlanStatus::Craft);
+        assert_eq!(root.children[0].item, "log");
+        assert_eq!(root.children[0].needed, 3);
+        assert_eq!(root.children[0].status, PlanStatus::InStock);
+    }
+
+    #[test]
+    fn detects_cycles() {
+        let root = resolve(
+            &[
+                ("block", pattern(&[("ingot", 9)], ("block", 1), None)),
+                ("ingot", pattern(&[("block", 1)], ("ingot", 9), None)),
+            ],
+            &[],
+            "block",
+            1,
+        );
+
+        let ingot = &root.children[0];
+        assert_eq!(ingot.status, PlanStatus::Craft);
+        assert_eq!(ingot.children[0].item, "block");
+        assert_eq!(ingot.children[0].status, PlanStatus::Cycle);
+        assert_eq!(ingot.children[0].missing, 1);
+        assert!(!CraftPlan { root }.is_craftable());
+    }
+
+    #[test]
+    fn reuses_leftovers_and_byproducts() {
+        // inputs are resolved by name, so the sticks are crafted before the rod, which then
+        // takes a leftover stick and the bucket the stick craft left behind
+        let root = resolve(
+            &[
+                (
+                    "tool",
+                    pattern(&[("stick", 2), ("tool_rod", 1)], ("tool", 1), None),
+                ),
+                (
+                    "tool_rod",
+                    pattern(&[("stick", 1), ("bucket", 1)], ("tool_rod", 1), None),
+                ),
+                (
+                    "stick",
+                    pattern(&[("planks", 2)], ("stick", 4), Some(("bucket", 1))),
+                ),
+            ],
+            &[("planks", 2)],
+            "tool",
+            1,
+        );
+
+        let [sticks, rod] = &root.children[..] else {
+            panic!("{:?}", root.children);
+        };
+        assert_eq!(sticks.item, "stick");
+        assert_eq!(sticks.crafts, 1);
+
+        let [bucket, rod_sticks] = &rod.children[..] else {
+            panic!("{:?}", rod.children);
+        };
+        // the bucket the stick craft left behind
+        assert_eq!(bucket.item, "bucket");
+        assert_eq!(bucket.from_stock, 1);
+        assert_eq!(bucket.status, PlanStatus::InStock);
+        // and one of the 2 sticks left over from it
+        assert_eq!(rod_sticks.from_stock, 1);
+        assert_eq!(rod_sticks.crafts, 0);
+
+        assert!(CraftPlan { root }.is_craftable());
+    }
+}
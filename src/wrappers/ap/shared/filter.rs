@@ -0,0 +1,136 @@
+use serde_json::Value;
+
+use super::{item::Item, nbt};
+
+/// Selects items (or fluids) in an RS or ME system by name or tag, and optionally NBT or fingerprint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemFilter {
+    name: String,
+    count: Option<usize>,
+    nbt: Option<String>,
+    fingerprint: Option<String>,
+}
+
+impl ItemFilter {
+    pub fn from_name(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            count: None,
+            nbt: None,
+            fingerprint: None,
+        }
+    }
+
+    pub fn from_tag(tag: impl ToString) -> Self {
+        let tag = tag.to_string();
+        let tag = if tag.starts_with('#') {
+            tag
+        } else {
+            format!("#{tag}")
+        };
+        Self {
+            name: tag,
+            count: None,
+            nbt: None,
+            fingerprint: None,
+        }
+    }
+
+    pub fn with_count(self, count: usize) -> Self {
+        Self {
+            count: Some(count),
+            ..self
+        }
+    }
+
+    pub fn with_nbt(self, nbt: impl ToString) -> Self {
+        Self {
+            nbt: Some(nbt.to_string()),
+            ..self
+        }
+    }
+
+    pub fn with_fingerprint(self, fingerprint: impl ToString) -> Self {
+        Self {
+            fingerprint: Some(fingerprint.to_string()),
+            ..self
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn count(&self) -> Option<usize> {
+        self.count
+    }
+
+    /// Checks an item listed by a bridge against the filter on the host, ignoring the count.
+    pub fn matches(&self, item: &Item) -> bool {
+        let name_matches = match self.name.strip_prefix('#') {
+            Some(tag) => item
+                .tags
+                .as_ref()
+                .is_some_and(|tags| tags.iter().any(|t| t == tag)),
+            None => item.name == self.name,
+        };
+        let nbt_matches = match (&self.nbt, &item.nbt) {
+            (None, _) => true,
+            (Some(nbt), Some(Value::String(item_nbt))) => nbt == item_nbt,
+            // the bridges return NBT as a table rather than the SNBT the filter is given as
+            (Some(snbt), Some(item_nbt)) => {
+                nbt::parse_snbt(snbt).is_some_and(|nbt| nbt::nbt_eq(&nbt, item_nbt))
+            }
+            (Some(_), None) => false,
+        };
+        let fingerprint_matches =
+            self.fingerprint.is_none() || self.fingerprint == item.fingerprint;
+
+        name_matches && nbt_matches && fingerprint_matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn item(nbt: Option<Value>, fingerprint: Option<&str>) -> Item {
+        Item {
+            name: "minecraft:potion".to_string(),
+            fingerprint: fingerprint.map(str::to_string),
+            amount: 1,
+            display_name: "Potion".to_string(),
+            is_craftable: false,
+            nbt,
+            tags: Some(vec!["minecraft:potions".to_string()]),
+        }
+    }
+
+    #[test]
+    fn matches_nbt_tables() {
+        let filter = ItemFilter::from_name("minecraft:potion")
+            .with_nbt(r#"{Potion:"minecraft:healing",CustomPotionColor:16711680}"#);
+
+        assert!(filter.matches(&item(
+            Some(json!({ "Potion": "minecraft:healing", "CustomPotionColor": 16711680 })),
+            None
+        )));
+        assert!(!filter.matches(&item(
+            Some(json!({ "Potion": "minecraft:swiftness", "CustomPotionColor": 16711680 })),
+            None
+        )));
+        assert!(!filter.matches(&item(None, None)));
+        assert!(ItemFilter::from_name("minecraft:potion").matches(&item(None, None)));
+    }
+
+    #[test]
+    fn matches_fingerprint_and_tag() {
+        let filter = ItemFilter::from_tag("minecraft:potions").with_fingerprint("ABC");
+
+        assert!(filter.matches(&item(None, Some("ABC"))));
+        assert!(!filter.matches(&item(None, Some("DEF"))));
+        assert!(!ItemFilter::from_tag("minecraft:logs").matches(&item(None, Some("ABC"))));
+    }
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Fluid {
+    pub name: String,
+    /// In millibuckets.
+    pub amount: u64,
+    pub display_name: String,
+    #[serde(default)]
+    pub is_craftable: bool,
+    pub tags: Option<Vec<String>>,
+}
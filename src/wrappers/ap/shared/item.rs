@@ -7,6 +7,7 @@ pub struct Item {
     pub fingerprint: Option<String>,
     pub amount: usize,
     pub display_name: String,
+    #[serde(default)]
     pub is_craftable: bool,
     pub nbt: Option<Value>,
     pub tags: Option<Vec<String>>,
@@ -0,0 +1,4 @@
+pub mod filter;
+pub mod fluid;
+pub mod item;
+pub(crate) mod nbt;
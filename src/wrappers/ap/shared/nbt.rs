@@ -0,0 +1,222 @@
+//! The bridges take NBT as SNBT text (i.e. `{Damage:0}`), but return it as a table converted
+//! from the item's tags, where numbers lose their type. These helpers let the host compare the two.
+
+use serde_json::{Map, Value};
+
+/// Parses SNBT into the same shape a bridge returns NBT in, or `None` if it isn't valid.
+pub(crate) fn parse_snbt(snbt: &str) -> Option<Value> {
+    let mut parser = Parser {
+        input: snbt.as_bytes(),
+        pos: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+
+    (parser.pos == parser.input.len()).then_some(value)
+}
+
+/// Compares NBT in the form the bridges return it, ignoring the type of numbers.
+pub(crate) fn nbt_eq(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(_) | Value::Bool(_), Value::Number(_) | Value::Bool(_)) => {
+            as_f64(a) == as_f64(b)
+        }
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| nbt_eq(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| nbt_eq(a, b)))
+        }
+        // an empty Lua table can't tell us whether it was a list or a compound
+        (Value::Array(list), Value::Object(compound))
+        | (Value::Object(compound), Value::Array(list)) => list.is_empty() && compound.is_empty(),
+        _ => false,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Bool(b) => Some(*b as u8 as f64),
+        Value::Number(n) => n.as_f64(),
+        _ => None,
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_whitespace())
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Option<()> {
+        (self.peek()? == c).then(|| self.pos += 1)
+    }
+
+    fn value(&mut self) -> Option<Value> {
+        match self.peek()? {
+            b'{' => self.compound(),
+            b'[' => self.list(),
+            b'"' | b'\'' => self.quoted().map(Value::String),
+            _ => Some(Self::unquoted_value(self.unquoted()?)),
+        }
+    }
+
+    fn compound(&mut self) -> Option<Value> {
+        self.expect(b'{')?;
+        let mut map = Map::new();
+        if self.peek()? == b'}' {
+            self.pos += 1;
+            return Some(Value::Object(map));
+        }
+
+        loop {
+            let key = match self.peek()? {
+                b'"' | b'\'' => self.quoted()?,
+                _ => self.unquoted()?,
+            };
+            self.expect(b':')?;
+            map.insert(key, self.value()?);
+
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b'}' => {
+                    self.pos += 1;
+                    return Some(Value::Object(map));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn list(&mut self) -> Option<Value> {
+        self.expect(b'[')?;
+        // typed arrays like `[I;1,2,3]` are returned as plain lists
+        if matches!(
+            self.input.get(self.pos..self.pos + 2),
+            Some([b'B' | b'I' | b'L', b';'])
+        ) {
+            self.pos += 2;
+        }
+
+        let mut list = Vec::new();
+        if self.peek()? == b']' {
+            self.pos += 1;
+            return Some(Value::Array(list));
+        }
+
+        loop {
+            list.push(self.value()?);
+
+            match self.peek()? {
+                b',' => self.pos += 1,
+                b']' => {
+                    self.pos += 1;
+                    return Some(Value::Array(list));
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Option<String> {
+        let quote = self.peek()?;
+        self.pos += 1;
+
+        let mut bytes = Vec::new();
+        loop {
+            let c = *self.input.get(self.pos)?;
+            self.pos += 1;
+            match c {
+                b'\\' => {
+                    bytes.push(*self.input.get(self.pos)?);
+                    self.pos += 1;
+                }
+                c if c == quote => return String::from_utf8(bytes).ok(),
+                c => bytes.push(c),
+            }
+        }
+    }
+
+    fn unquoted(&mut self) -> Option<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_alphanumeric() || b"_-.+".contains(c))
+        {
+            self.pos += 1;
+        }
+
+        (self.pos > start).then(|| String::from_utf8_lossy(&self.input[start..self.pos]).into())
+    }
+
+    fn unquoted_value(text: String) -> Value {
+        match text.as_str() {
+            "true" => return Value::from(1),
+            "false" => return Value::from(0),
+            _ => {}
+        }
+
+        let number = text
+            .strip_suffix(|c: char| "bBsSlLfFdD".contains(c))
+            .unwrap_or(&text);
+        if let Ok(n) = number.parse::<i64>() {
+            return Value::from(n);
+        }
+        match number.parse::<f64>() {
+            Ok(n) if n.is_finite() => Value::from(n),
+            _ => Value::String(text),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_snbt() {
+        assert_eq!(
+            parse_snbt(r#"{Damage:3s, display:{Name:'{"text":"Hi"}'}, ids:[I;1,2], ok:1b}"#),
+            Some(json!({
+                "Damage": 3,
+                "display": { "Name": "{\"text\":\"Hi\"}" },
+                "ids": [1, 2],
+                "ok": 1,
+            }))
+        );
+        assert_eq!(parse_snbt("{Damage:3"), None);
+        assert_eq!(parse_snbt("{} trailing"), None);
+    }
+
+    #[test]
+    fn compares_numbers_loosely() {
+        assert!(nbt_eq(
+            &json!({ "a": 1.0, "b": [] }),
+            &json!({ "a": 1, "b": {} })
+        ));
+        assert!(!nbt_eq(&json!({ "a": 1 }), &json!({ "a": 1, "b": 2 })));
+        assert!(!nbt_eq(&json!({ "a": "1" }), &json!({ "a": 1 })));
+    }
+}
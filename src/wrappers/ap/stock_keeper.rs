@@ -0,0 +1,250 @@
+use std::{collections::HashMap, time::Duration};
+
+use futures_util::Stream;
+
+use crate::error::Result;
+
+use super::{
+    shared::{filter::ItemFilter, item::Item},
+    storage_network::StorageNetwork,
+};
+
+/// Keeps at least `minimum` of the items matching a filter in stock.
+#[derive(Debug, Clone)]
+pub struct StockRule {
+    filter: ItemFilter,
+    craft: Option<ItemFilter>,
+    minimum: usize,
+    batch_size: usize,
+    max_craft: Option<usize>,
+}
+
+impl StockRule {
+    pub fn new(filter: ItemFilter, minimum: usize) -> Self {
+        Self {
+            filter,
+            craft: None,
+            minimum,
+            batch_size: 1,
+            max_craft: None,
+        }
+    }
+
+    /// Crafts this item instead of the filter's when there is a shortfall. Required for rules
+    /// that count by tag, since a tag can't be crafted.
+    pub fn craft_as(self, craft: ItemFilter) -> Self {
+        Self {
+            craft: Some(craft),
+            ..self
+        }
+    }
+
+    /// Rounds every craft up to a multiple of `batch_size` (i.e. the output of one recipe).
+    pub fn with_batch_size(self, batch_size: usize) -> Self {
+        Self {
+            batch_size: batch_size.max(1),
+            ..self
+        }
+    }
+
+    /// Limits how many items a single craft can request.
+    pub fn with_max_craft(self, max_craft: usize) -> Self {
+        Self {
+            max_craft: Some(max_craft),
+            ..self
+        }
+    }
+
+    pub fn filter(&self) -> &ItemFilter {
+        &self.filter
+    }
+
+    fn craft_filter(&self) -> &ItemFilter {
+        self.craft.as_ref().unwrap_or(&self.filter)
+    }
+
+    fn craft_amount(&self, stored: usize) -> usize {
+        let shortfall = self.minimum - stored;
+        let amount = shortfall.div_ceil(self.batch_size) * self.batch_size;
+        match self.max_craft {
+            Some(max) => amount.min(max),
+            None => amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StockEvent {
+    CraftStarted {
+        item: String,
+        stored: usize,
+        amount: usize,
+    },
+    /// The system refused the craft, usually because of missing ingredients or patterns.
+    CraftFailed {
+        item: String,
+        stored: usize,
+        amount: usize,
+    },
+    /// The item is below its minimum but is already being crafted.
+    AlreadyCrafting { item: String, stored: usize },
+    /// A craft started by the stock keeper is no longer running, and at least the
+    /// requested amount was added to what was stored when it started.
+    CraftFinished { item: String, stored: usize },
+    /// A craft started by the stock keeper is no longer running, but fewer items than
+    /// expected are stored (i.e. it was cancelled, or items were taken out while it ran).
+    CraftIncomplete {
+        item: String,
+        stored: usize,
+        expected: usize,
+    },
+}
+
+/// Polls a [`StorageNetwork`] and crafts anything that falls below its [`StockRule`] minimum.
+#[derive(Debug, Clone)]
+pub struct StockKeeper {
+    rules: Vec<StockRule>,
+    interval: Duration,
+    // rules with a craft we started that hasn't finished yet, by index, and
+    // how many items should be stored once it has
+    crafting: HashMap<usize, usize>,
+}
+
+impl Default for StockKeeper {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            interval: Duration::from_secs(10),
+            crafting: HashMap::new(),
+        }
+    }
+}
+
+impl StockKeeper {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rule(mut self, rule: StockRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// How long to wait between checks. Defaults to 10 seconds.
+    pub fn interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+
+    pub fn rules(&self) -> &[StockRule] {
+        &self.rules
+    }
+
+    /// Checks every rule once, starting crafts for any shortfalls.
+    pub async fn check<N: StorageNetwork + ?Sized>(
+        &mut self,
+        network: &N,
+    ) -> Result<Vec<StockEvent>> {
+        let items = network.list_items().await?;
+        let mut events = Vec::new();
+
+        for (i, rule) in self.rules.iter().enumerate() {
+            let stored = Self::stored(&items, &rule.filter);
+            let item = rule.craft_filter().name().to_string();
+
+            // nothing to do for a rule that is stocked and isn't waiting on a craft
+            if stored >= rule.minimum && !self.crafting.contains_key(&i) {
+                continue;
+            }
+
+            let crafting = network
+                .is_item_crafting(rule.craft_filter().clone())
+                .await?;
+            if !crafting {
+                if let Some(expected) = self.crafting.remove(&i) {
+                    events.push(if stored >= expected {
+                        StockEvent::CraftFinished {
+                            item: item.clone(),
+                            stored,
+                        }
+                    } else {
+                        StockEvent::CraftIncomplete {
+                            item: item.clone(),
+                            stored,
+                            expected,
+                        }
+                    });
+                }
+            }
+
+            if stored >= rule.minimum {
+                continue;
+            }
+            if crafting {
+                events.push(StockEvent::AlreadyCrafting { item, stored });
+                continue;
+            }
+
+            let amount = rule.craft_amount(stored);
+            let filter = rule.craft_filter().clone().with_count(amount);
+            if network.craft_item(filter).await? {
+                self.crafting.insert(i, stored + amount);
+                events.push(StockEvent::CraftStarted {
+                    item,
+                    stored,
+                    amount,
+                });
+            } else {
+                events.push(StockEvent::CraftFailed {
+                    item,
+                    stored,
+                    amount,
+                });
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn stored(items: &[Item], filter: &ItemFilter) -> usize {
+        items
+            .iter()
+            .filter(|item| filter.matches(item))
+            .map(|item| item.amount)
+            .sum()
+    }
+
+    /// Checks the rules every [`StockKeeper::interval`], yielding what happened. The stream
+    /// ends after the first error (i.e. the computer disconnecting).
+    pub fn run<'n, N: StorageNetwork + ?Sized>(
+        self,
+        network: &'n N,
+    ) -> impl Stream<Item = Result<StockEvent>> + Send + Unpin + 'n {
+        let state = (self, network, Vec::new().into_iter(), true, false);
+        Box::pin(futures_util::stream::unfold(
+            state,
+            |(mut keeper, network, mut pending, mut first, failed)| async move {
+                if failed {
+                    return None;
+                }
+
+                loop {
+                    if let Some(event) = pending.next() {
+                        return Some((Ok(event), (keeper, network, pending, first, false)));
+                    }
+
+                    if !first {
+                        tokio::time::sleep(keeper.interval).await;
+                    }
+                    first = false;
+
+                    match keeper.check(network).await {
+                        Ok(events) => pending = events.into_iter(),
+                        Err(err) => {
+                            return Some((Err(err), (keeper, network, pending, first, true)))
+                        }
+                    }
+                }
+            },
+        ))
+    }
+}
@@ -0,0 +1,133 @@
+use async_trait::async_trait;
+
+use crate::{error::Result, wrappers::shared::direction::Direction};
+
+use super::{
+    me_bridge::MeBridge,
+    rs_bridge::RsBridge,
+    shared::{filter::ItemFilter, fluid::Fluid, item::Item},
+};
+
+/// The methods shared by the RS and ME bridges, so code can be written against either mod.
+///
+/// Energy is in each mod's own unit (FE for RS, AE for ME).
+#[async_trait]
+pub trait StorageNetwork: Send + Sync {
+    async fn list_items(&self) -> Result<Vec<Item>>;
+
+    async fn get_item(&self, item: ItemFilter) -> Result<Option<Item>>;
+
+    async fn list_craftable_items(&self) -> Result<Vec<Item>>;
+
+    async fn list_fluids(&self) -> Result<Vec<Fluid>>;
+
+    async fn craft_item(&self, item: ItemFilter) -> Result<bool>;
+
+    async fn is_item_crafting(&self, item: ItemFilter) -> Result<bool>;
+
+    async fn export_item(&self, item: ItemFilter, direction: Direction) -> Result<usize>;
+
+    async fn import_item(&self, item: ItemFilter, direction: Direction) -> Result<usize>;
+
+    async fn export_item_to_peripheral(&self, item: ItemFilter, container: String)
+        -> Result<usize>;
+
+    async fn import_item_from_peripheral(
+        &self,
+        item: ItemFilter,
+        container: String,
+    ) -> Result<usize>;
+
+    async fn get_energy_storage(&self) -> Result<f64>;
+
+    async fn get_max_energy_storage(&self) -> Result<f64>;
+
+    async fn get_energy_usage(&self) -> Result<f64>;
+
+    /// The amount of an item stored, 0 if there is none.
+    async fn get_item_amount(&self, item: ItemFilter) -> Result<usize> {
+        Ok(self.get_item(item).await?.map_or(0, |item| item.amount))
+    }
+}
+
+macro_rules! impl_storage_network {
+    ($ty:ident, craft = |$this:ident, $item:ident| $craft:expr, is_crafting = |$this2:ident, $item2:ident| $is_crafting:expr) => {
+        #[async_trait]
+        impl<'a> StorageNetwork for $ty<'a> {
+            async fn list_items(&self) -> Result<Vec<Item>> {
+                $ty::list_items(self).await
+            }
+
+            async fn get_item(&self, item: ItemFilter) -> Result<Option<Item>> {
+                $ty::get_item(self, item).await
+            }
+
+            async fn list_craftable_items(&self) -> Result<Vec<Item>> {
+                $ty::list_craftable_items(self).await
+            }
+
+            async fn list_fluids(&self) -> Result<Vec<Fluid>> {
+                $ty::list_fluids(self).await
+            }
+
+            async fn craft_item(&self, $item: ItemFilter) -> Result<bool> {
+                let $this = self;
+                $craft.await
+            }
+
+            async fn is_item_crafting(&self, $item2: ItemFilter) -> Result<bool> {
+                let $this2 = self;
+                $is_crafting.await
+            }
+
+            async fn export_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+                $ty::export_item(self, item, direction).await
+            }
+
+            async fn import_item(&self, item: ItemFilter, direction: Direction) -> Result<usize> {
+                $ty::import_item(self, item, direction).await
+            }
+
+            async fn export_item_to_peripheral(
+                &self,
+                item: ItemFilter,
+                container: String,
+            ) -> Result<usize> {
+                $ty::export_item_to_peripheral(self, item, container).await
+            }
+
+            async fn import_item_from_peripheral(
+                &self,
+                item: ItemFilter,
+                container: String,
+            ) -> Result<usize> {
+                $ty::import_item_from_peripheral(self, item, container).await
+            }
+
+            async fn get_energy_storage(&self) -> Result<f64> {
+                Ok($ty::get_energy_storage(self).await? as f64)
+            }
+
+            async fn get_max_energy_storage(&self) -> Result<f64> {
+                Ok($ty::get_max_energy_storage(self).await? as f64)
+            }
+
+            async fn get_energy_usage(&self) -> Result<f64> {
+                $ty::get_energy_usage(self).await
+            }
+        }
+    };
+}
+
+impl_storage_network!(
+    RsBridge,
+    craft = |bridge, item| RsBridge::craft_item(bridge, item),
+    is_crafting = |bridge, item| RsBridge::is_item_crafting(bridge, item)
+);
+
+// the trait always lets the ME system pick a crafting CPU
+impl_storage_network!(
+    MeBridge,
+    craft = |bridge, item| MeBridge::craft_item(bridge, item, None),
+    is_crafting = |bridge, item| MeBridge::is_item_crafting(bridge, item, None)
+);
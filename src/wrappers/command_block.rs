@@ -0,0 +1,20 @@
+use super::prelude::*;
+
+generate_wrapper_impl!(CommandBlock = "command");
+
+impl<'a> CommandBlock<'a> {
+    pub async fn get_command(&self) -> Result<String> {
+        self.inner.call_method_with("getCommand", Value::Null).await
+    }
+
+    generate_wrapped_fn!(set_command -> void = |command: impl ToString| => setCommand(command.to_string()));
+
+    /// Runs the command block's command, returning whether it succeeded and the error if it didn't.
+    pub async fn run_command(&self) -> Result<(bool, Option<String>)> {
+        match &self.inner.call_method("runCommand", Value::Null).await?[..] {
+            [Value::Bool(success)] => Ok((*success, None)),
+            [Value::Bool(success), Value::String(error)] => Ok((*success, Some(error.clone()))),
+            ret => crate::debug_feature!(Err(crate::error::Error::UnexpectedData(ret.to_vec()))),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{api::fs::Fs, wrappers::prelude::*};
+
+generate_wrapper_impl!(Drive = "drive");
+
+impl<'a> Drive<'a> {
+    generate_wrapped_fn!(
+        is_disk_present -> bool = | | => isDiskPresent(Value::Null);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    pub async fn get_disk_label(&self) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getDiskLabel", Value::Null)
+            .await
+    }
+
+    generate_wrapped_fn!(set_disk_label -> void = |label: impl ToString| => setDiskLabel(label.to_string()));
+
+    generate_wrapped_fn!(clear_disk_label -> void = | | => setDiskLabel(Value::Null));
+
+    generate_wrapped_fn!(
+        has_data -> bool = | | => hasData(Value::Null);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    /// The path the disk is mounted at, or `None` if there is no floppy disk in the drive.
+    pub async fn get_mount_path(&self) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getMountPath", Value::Null)
+            .await
+    }
+
+    /// A filesystem API scoped to the disk's mount path, or `None` if there is no floppy disk in the drive.
+    pub async fn fs(&self) -> Result<Option<Fs<'a>>> {
+        Ok(self
+            .get_mount_path()
+            .await?
+            .map(|path| self.inner.computer.fs().scoped(path)))
+    }
+
+    generate_wrapped_fn!(
+        has_audio -> bool = | | => hasAudio(Value::Null);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    /// The title of the music disc in the drive, or `None` if there is no music disc.
+    pub async fn get_audio_title(&self) -> Result<Option<String>> {
+        match &self.inner.call_method("getAudioTitle", Value::Null).await?[..] {
+            [Value::String(title)] => Ok(Some(title.clone())),
+            // false means there is a disk, but it isn't a music disc
+            [] | [Value::Null] | [Value::Bool(false)] => Ok(None),
+            ret => crate::debug_feature!(Err(crate::error::Error::UnexpectedData(ret.to_vec()))),
+        }
+    }
+
+    generate_wrapped_fn!(play_audio -> void = | | => playAudio(Value::Null));
+
+    generate_wrapped_fn!(stop_audio -> void = | | => stopAudio(Value::Null));
+
+    generate_wrapped_fn!(eject_disk -> void = | | => ejectDisk(Value::Null));
+
+    /// The ID of the floppy disk in the drive, or `None` if there is no floppy disk.
+    pub async fn get_disk_id(&self) -> Result<Option<u64>> {
+        self.inner
+            .call_method_with_optional("getDiskID", Value::Null)
+            .await
+    }
+}
@@ -0,0 +1,30 @@
+use super::prelude::*;
+
+generate_wrapper_impl!(EnergyStorage = "energy_storage");
+
+impl<'a> EnergyStorage<'a> {
+    pub fn address(&self) -> &str {
+        &self.inner.address
+    }
+
+    /// The energy stored, in FE.
+    pub async fn get_energy(&self) -> Result<u64> {
+        self.inner.call_method_with("getEnergy", Value::Null).await
+    }
+
+    pub async fn get_energy_capacity(&self) -> Result<u64> {
+        self.inner
+            .call_method_with("getEnergyCapacity", Value::Null)
+            .await
+    }
+
+    /// How full the storage is, between 0.0 and 1.0.
+    pub async fn fill_fraction(&self) -> Result<f64> {
+        let capacity = self.get_energy_capacity().await?;
+        if capacity == 0 {
+            return Ok(0.0);
+        }
+
+        Ok(self.get_energy().await? as f64 / capacity as f64)
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use super::prelude::*;
+use crate::lua_compat::LuaSparseVec;
+
+generate_wrapper_impl!(FluidStorage = "fluid_storage");
+
+/// The contents of a tank, in millibuckets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FluidStack {
+    pub name: String,
+    pub amount: u64,
+}
+
+impl<'a> FluidStorage<'a> {
+    pub fn address(&self) -> &str {
+        &self.inner.address
+    }
+
+    /// Lists the contents of every non-empty tank, keyed by 1-based tank number.
+    pub async fn tanks(&self) -> Result<BTreeMap<usize, FluidStack>> {
+        let tanks: LuaSparseVec<FluidStack> =
+            self.inner.call_method_with("tanks", Value::Null).await?;
+
+        Ok(tanks.0)
+    }
+
+    /// Moves up to `limit` millibuckets of fluid (only `fluid` if given) into the fluid storage
+    /// named `to`, which must be on the same wired network. Returns the amount moved.
+    pub async fn push_fluid(
+        &self,
+        to: impl ToString,
+        limit: Option<u64>,
+        fluid: Option<&str>,
+    ) -> Result<u64> {
+        self.inner
+            .call_method_with(
+                "pushFluid",
+                (to.to_string(), limit, fluid.map(str::to_string)),
+            )
+            .await
+    }
+
+    /// Moves up to `limit` millibuckets of fluid (only `fluid` if given) from the fluid storage
+    /// named `from` into this one. Returns the amount moved.
+    pub async fn pull_fluid(
+        &self,
+        from: impl ToString,
+        limit: Option<u64>,
+        fluid: Option<&str>,
+    ) -> Result<u64> {
+        self.inner
+            .call_method_with(
+                "pullFluid",
+                (from.to_string(), limit, fluid.map(str::to_string)),
+            )
+            .await
+    }
+
+    /// Like [`FluidStorage::push_fluid`], but with the destination as a wrapped fluid storage.
+    pub async fn push_to(
+        &self,
+        to: &FluidStorage<'_>,
+        limit: Option<u64>,
+        fluid: Option<&str>,
+    ) -> Result<u64> {
+        self.push_fluid(to.address(), limit, fluid).await
+    }
+
+    /// Like [`FluidStorage::pull_fluid`], but with the source as a wrapped fluid storage.
+    pub async fn pull_from(
+        &self,
+        from: &FluidStorage<'_>,
+        limit: Option<u64>,
+        fluid: Option<&str>,
+    ) -> Result<u64> {
+        self.pull_fluid(from.address(), limit, fluid).await
+    }
+
+    /// The total amount of fluid in every tank, in millibuckets.
+    pub async fn total_amount(&self) -> Result<u64> {
+        Ok(self.tanks().await?.values().map(|tank| tank.amount).sum())
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use super::prelude::*;
+use crate::lua_compat::LuaSparseVec;
+
+mod item_stack;
+pub use item_stack::*;
+mod storage_system;
+pub use storage_system::*;
+
+generate_wrapper_impl!(Inventory = "inventory");
+
+impl<'a> Inventory<'a> {
+    pub fn address(&self) -> &str {
+        &self.inner.address
+    }
+
+    /// The number of slots in the inventory.
+    pub async fn size(&self) -> Result<usize> {
+        self.inner.call_method_with("size", Value::Null).await
+    }
+
+    /// Lists the items in every non-empty slot, keyed by 1-based slot number.
+    pub async fn list(&self) -> Result<BTreeMap<usize, ItemStack>> {
+        let items: LuaSparseVec<ItemStack> =
+            self.inner.call_method_with("list", Value::Null).await?;
+
+        Ok(items.0)
+    }
+
+    pub async fn get_item_detail(&self, slot: usize) -> Result<Option<ItemDetail>> {
+        self.inner
+            .call_method_with_optional("getItemDetail", slot)
+            .await
+    }
+
+    /// The maximum number of items that can be stored in `slot`.
+    pub async fn get_item_limit(&self, slot: usize) -> Result<u32> {
+        self.inner.call_method_with("getItemLimit", slot).await
+    }
+
+    /// Moves up to `limit` items from `from_slot` into the inventory named `to`, which must be
+    /// on the same wired network. Returns the number of items moved.
+    pub async fn push_items(
+        &self,
+        to: impl ToString,
+        from_slot: usize,
+        limit: Option<u32>,
+        to_slot: Option<usize>,
+    ) -> Result<u32> {
+        self.inner
+            .call_method_with("pushItems", (to.to_string(), from_slot, limit, to_slot))
+            .await
+    }
+
+    /// Moves up to `limit` items from `from_slot` of the inventory named `from` into this one.
+    /// Returns the number of items moved.
+    pub async fn pull_items(
+        &self,
+        from: impl ToString,
+        from_slot: usize,
+        limit: Option<u32>,
+        to_slot: Option<usize>,
+    ) -> Result<u32> {
+        self.inner
+            .call_method_with("pullItems", (from.to_string(), from_slot, limit, to_slot))
+            .await
+    }
+
+    /// Like [`Inventory::push_items`], but with the destination as a wrapped inventory.
+    pub async fn push_to(
+        &self,
+        to: &Inventory<'_>,
+        from_slot: usize,
+        limit: Option<u32>,
+        to_slot: Option<usize>,
+    ) -> Result<u32> {
+        self.push_items(to.address(), from_slot, limit, to_slot)
+            .await
+    }
+
+    /// Like [`Inventory::pull_items`], but with the source as a wrapped inventory.
+    pub async fn pull_from(
+        &self,
+        from: &Inventory<'_>,
+        from_slot: usize,
+        limit: Option<u32>,
+        to_slot: Option<usize>,
+    ) -> Result<u32> {
+        self.pull_items(from.address(), from_slot, limit, to_slot)
+            .await
+    }
+
+    /// Moves every item in this inventory into `to`, returning the number of items moved.
+    pub async fn push_all_to(&self, to: &Inventory<'_>) -> Result<u32> {
+        let mut moved = 0;
+        for slot in self.list().await?.into_keys() {
+            moved += self.push_to(to, slot, None, None).await?;
+        }
+
+        Ok(moved)
+    }
+}
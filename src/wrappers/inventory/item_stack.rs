@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+/// An item in an inventory slot, as returned by [`Inventory::list`](super::Inventory::list).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemStack {
+    pub name: String,
+    pub count: u32,
+    /// A hash of the item's NBT, if it has any.
+    pub nbt: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Enchantment {
+    pub name: String,
+    pub level: u32,
+    pub display_name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemGroup {
+    pub id: String,
+    pub display_name: String,
+}
+
+/// Everything known about an item, as returned by [`Inventory::get_item_detail`](super::Inventory::get_item_detail).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDetail {
+    pub name: String,
+    pub count: u32,
+    pub nbt: Option<String>,
+    pub display_name: String,
+    pub max_count: u32,
+    #[serde(default)]
+    pub tags: HashMap<String, bool>,
+    /// Only present for items that can be damaged.
+    pub damage: Option<u32>,
+    pub max_damage: Option<u32>,
+    /// Between 0.0 and 1.0, only present for damaged items.
+    pub durability: Option<f64>,
+    #[serde(default, deserialize_with = "crate::lua_compat::deserialize_with")]
+    pub enchantments: Vec<Enchantment>,
+    #[serde(default, deserialize_with = "crate::lua_compat::deserialize_with")]
+    pub lore: Vec<String>,
+    #[serde(default, deserialize_with = "crate::lua_compat::deserialize_with")]
+    pub item_groups: Vec<ItemGroup>,
+    #[serde(default)]
+    pub unbreakable: bool,
+}
+
+impl ItemStack {
+    /// Returns true if `other` is the same item with the same NBT, and could be stacked with this one.
+    pub fn is_same_item(&self, other: &ItemStack) -> bool {
+        self.name == other.name && self.nbt == other.nbt
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{computer::Computer, error::Result, wrappers::modem::Modem};
+
+use super::{Inventory, ItemStack};
+
+/// Matches items by name, and optionally by their NBT hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemQuery {
+    pub name: String,
+    pub nbt: Option<String>,
+}
+
+impl ItemQuery {
+    pub fn from_name(name: impl ToString) -> Self {
+        Self {
+            name: name.to_string(),
+            nbt: None,
+        }
+    }
+
+    pub fn with_nbt(mut self, nbt: impl ToString) -> Self {
+        self.nbt = Some(nbt.to_string());
+        self
+    }
+
+    pub fn matches(&self, item: &ItemStack) -> bool {
+        item.name == self.name && (self.nbt.is_none() || item.nbt == self.nbt)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct IndexedInventory<'a> {
+    inventory: Inventory<'a>,
+    size: usize,
+    slots: BTreeMap<usize, ItemStack>,
+    // from `getItemLimit`, only fetched for slots that are inserted into
+    slot_limits: HashMap<usize, u32>,
+}
+
+impl IndexedInventory<'_> {
+    async fn slot_limit(&mut self, slot: usize) -> Result<u32> {
+        if let Some(limit) = self.slot_limits.get(&slot) {
+            return Ok(*limit);
+        }
+
+        let limit = self.inventory.get_item_limit(slot).await?;
+        self.slot_limits.insert(slot, limit);
+
+        Ok(limit)
+    }
+}
+
+/// Treats many inventories (i.e. every chest on a wired network) as one.
+///
+/// The contents of every inventory are cached on the host when the system is created and
+/// kept up to date by its own transfers. Call [`StorageSystem::refresh`] if anything else
+/// may have changed them.
+#[derive(Debug, Clone)]
+pub struct StorageSystem<'a> {
+    inventories: Vec<IndexedInventory<'a>>,
+    // keyed by (name, nbt), from `maxCount` in the item's details
+    stack_limits: HashMap<(String, Option<String>), u32>,
+}
+
+impl<'a> StorageSystem<'a> {
+    pub async fn new(inventories: Vec<Inventory<'a>>) -> Result<Self> {
+        let mut system = Self {
+            inventories: inventories
+                .into_iter()
+                .map(|inventory| IndexedInventory {
+                    inventory,
+                    size: 0,
+                    slots: BTreeMap::new(),
+                    slot_limits: HashMap::new(),
+                })
+                .collect(),
+            stack_limits: HashMap::new(),
+        };
+        system.refresh().await?;
+
+        Ok(system)
+    }
+
+    /// Indexes every inventory on the wired network of `modem`, except those named in `exclude`
+    /// (i.e. the chests used for input and output).
+    pub async fn from_network(
+        computer: &'a Computer,
+        modem: &Modem<'_>,
+        exclude: &[&str],
+    ) -> Result<Self> {
+        let mut inventories = Vec::new();
+        for name in modem.get_names_remote().await? {
+            if exclude.contains(&name.as_str())
+                || !modem.has_type_remote(&name, "inventory").await?
+            {
+                continue;
+            }
+
+            inventories.push(computer.find_peripheral(name).await?.as_wrapper().await?);
+        }
+
+        Self::new(inventories).await
+    }
+
+    pub fn inventories(&self) -> impl Iterator<Item = &Inventory<'a>> {
+        self.inventories.iter().map(|indexed| &indexed.inventory)
+    }
+
+    /// Lists every inventory again, replacing the cache.
+    pub async fn refresh(&mut self) -> Result<()> {
+        for indexed in &mut self.inventories {
+            indexed.size = indexed.inventory.size().await?;
+            indexed.slots = indexed.inventory.list().await?;
+            indexed.slot_limits.clear();
+        }
+
+        Ok(())
+    }
+
+    /// The total of every item in the system, by name and NBT.
+    pub fn items(&self) -> Vec<ItemStack> {
+        let mut totals: BTreeMap<(String, Option<String>), u32> = BTreeMap::new();
+        for stack in self.stacks() {
+            *totals
+                .entry((stack.name.clone(), stack.nbt.clone()))
+                .or_default() += stack.count;
+        }
+
+        totals
+            .into_iter()
+            .map(|((name, nbt), count)| ItemStack { name, count, nbt })
+            .collect()
+    }
+
+    pub fn count(&self, query: &ItemQuery) -> u64 {
+        self.stacks()
+            .filter(|stack| query.matches(stack))
+            .map(|stack| stack.count as u64)
+            .sum()
+    }
+
+    /// The number of slots with nothing in them.
+    pub fn free_slots(&self) -> usize {
+        self.inventories
+            .iter()
+            .map(|indexed| indexed.size - indexed.slots.len())
+            .sum()
+    }
+
+    fn stacks(&self) -> impl Iterator<Item = &ItemStack> {
+        self.inventories
+            .iter()
+            .flat_map(|indexed| indexed.slots.values())
+    }
+
+    /// Moves up to `count` items matching `query` into `to`, which must be on the same wired
+    /// network, returning the number moved. Stops early if `to` is full.
+    ///
+    /// Every slot is looked at again before items are taken from it, and the cache is
+    /// corrected for any that no longer hold a matching item.
+    pub async fn extract(
+        &mut self,
+        query: &ItemQuery,
+        count: u32,
+        to: &Inventory<'_>,
+    ) -> Result<u32> {
+        let mut moved = 0;
+
+        'inventories: for indexed in &mut self.inventories {
+            let matching = indexed
+                .slots
+                .iter()
+                .filter(|(_, stack)| query.matches(stack))
+                .map(|(slot, _)| *slot)
+                .collect::<Vec<_>>();
+
+            for slot in matching {
+                if moved >= count {
+                    break 'inventories;
+                }
+
+                // pushing from a stale slot would move whatever is in it now instead
+                let current = indexed
+                    .inventory
+                    .get_item_detail(slot)
+                    .await?
+                    .map(|detail| ItemStack {
+                        name: detail.name,
+                        count: detail.count,
+                        nbt: detail.nbt,
+                    });
+                let mut stack = match current {
+                    Some(stack) if query.matches(&stack) => stack,
+                    Some(stack) => {
+                        indexed.slots.insert(slot, stack);
+                        continue;
+                    }
+                    None => {
+                        indexed.slots.remove(&slot);
+                        continue;
+                    }
+                };
+
+                let pushed = indexed
+                    .inventory
+                    .push_to(to, slot, Some(count - moved), None)
+                    .await?;
+                moved += pushed;
+                stack.count = stack.count.saturating_sub(pushed);
+                if stack.count == 0 {
+                    indexed.slots.remove(&slot);
+                } else {
+                    indexed.slots.insert(slot, stack);
+                }
+
+                if pushed == 0 {
+                    // the slot holds the item, so `to` must be full
+                    break 'inventories;
+                }
+            }
+        }
+
+        Ok(moved)
+    }
+
+    /// Moves everything in `from`, which must not be part of the system, into the system. Existing
+    /// stacks of the same item are topped up before empty slots are filled. Returns the number of
+    /// items moved, which is less than the number in `from` if the system is full.
+    pub async fn insert(&mut self, from: &Inventory<'_>) -> Result<u32> {
+        let mut moved = 0;
+        for (from_slot, stack) in from.list().await? {
+            moved += self.insert_stack(from, from_slot, &stack).await?;
+        }
+
+        Ok(moved)
+    }
+
+    async fn insert_stack(
+        &mut self,
+        from: &Inventory<'_>,
+        from_slot: usize,
+        stack: &ItemStack,
+    ) -> Result<u32> {
+        let limit = self.stack_limit(from, from_slot, stack).await?;
+        let mut remaining = stack.count;
+
+        // partial stacks of the same item first, then empty slots
+        let mut targets = Vec::new();
+        for (i, indexed) in self.inventories.iter().enumerate() {
+            for (slot, existing) in &indexed.slots {
+                if existing.is_same_item(stack) && existing.count < limit {
+                    targets.push((i, *slot, existing.count));
+                }
+            }
+        }
+        for (i, indexed) in self.inventories.iter().enumerate() {
+            for slot in 1..=indexed.size {
+                if !indexed.slots.contains_key(&slot) {
+                    targets.push((i, slot, 0));
+                }
+            }
+        }
+
+        for (i, slot, existing) in targets {
+            if remaining == 0 {
+                break;
+            }
+
+            let indexed = &mut self.inventories[i];
+            let space = limit
+                .min(indexed.slot_limit(slot).await?)
+                .saturating_sub(existing);
+            if space == 0 {
+                continue;
+            }
+
+            let pulled = indexed
+                .inventory
+                .pull_from(from, from_slot, Some(remaining.min(space)), Some(slot))
+                .await?;
+            if pulled == 0 {
+                continue;
+            }
+
+            remaining -= pulled;
+            indexed
+                .slots
+                .entry(slot)
+                .and_modify(|existing| existing.count += pulled)
+                .or_insert_with(|| ItemStack {
+                    count: pulled,
+                    ..stack.clone()
+                });
+        }
+
+        Ok(stack.count - remaining)
+    }
+
+    async fn stack_limit(
+        &mut self,
+        from: &Inventory<'_>,
+        from_slot: usize,
+        stack: &ItemStack,
+    ) -> Result<u32> {
+        let key = (stack.name.clone(), stack.nbt.clone());
+        if let Some(limit) = self.stack_limits.get(&key) {
+            return Ok(*limit);
+        }
+
+        let limit = match from.get_item_detail(from_slot).await? {
+            Some(detail) => detail.max_count,
+            // the slot was emptied by something else, the transfer will move nothing anyway
+            None => return Ok(stack.count),
+        };
+        self.stack_limits.insert(key, limit);
+
+        Ok(limit)
+    }
+}
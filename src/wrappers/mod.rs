@@ -1,7 +1,17 @@
-pub mod lua_compat;
+pub mod command_block;
+pub mod drive;
+pub mod energy_storage;
+pub mod fluid_storage;
+pub mod inventory;
+pub mod modem;
 pub mod monitor;
 pub mod printer;
+pub mod remote_computer;
 pub mod shared;
+pub mod speaker;
+
+// kept here so existing `wrappers::lua_compat` paths keep working
+pub use crate::lua_compat;
 
 #[cfg(feature = "advanced-peripherals")]
 pub mod ap;
@@ -28,6 +38,8 @@ macro_rules! generate_wrapped_fn {
 }
 
 use async_trait::async_trait;
+
+use crate::peripheral::{Peripheral, PeripheralWrapper};
 pub(crate) use generate_wrapped_fn;
 
 #[async_trait]
@@ -35,29 +47,25 @@ pub trait IntoWrappedPeripheral<W> {
     async fn into_wrapped(self) -> crate::error::Result<W>;
 }
 
+#[async_trait]
+impl<'a, W: PeripheralWrapper<'a> + Send> IntoWrappedPeripheral<W> for Peripheral<'a> {
+    async fn into_wrapped(self) -> crate::error::Result<W> {
+        self.as_wrapper().await
+    }
+}
+
 macro_rules! generate_wrapper_impl {
     ($wrapper_ty:ident = $expected_ty:literal) => {
-        #[derive(Debug)]
+        #[derive(Debug, Clone)]
         pub struct $wrapper_ty<'a> {
             inner: Peripheral<'a>,
         }
 
-        #[async_trait]
-        impl<'a> IntoWrappedPeripheral<$wrapper_ty<'a>> for Peripheral<'a> {
-            async fn into_wrapped(self) -> $crate::error::Result<$wrapper_ty<'a>> {
-                let ty = self
-                    .computer
-                    .get_peripheral_type(self.address.clone())
-                    .await?;
-
-                if ty != $expected_ty {
-                    return $crate::debug_feature!(Err($crate::error::Error::WrongPeripheralType(
-                        ty,
-                        $expected_ty.into(),
-                    )));
-                }
+        impl<'a> PeripheralWrapper<'a> for $wrapper_ty<'a> {
+            const TYPE: &'static str = $expected_ty;
 
-                Ok($wrapper_ty { inner: self })
+            fn wrap_unchecked(peripheral: Peripheral<'a>) -> Self {
+                Self { inner: peripheral }
             }
         }
     };
@@ -66,14 +74,11 @@ macro_rules! generate_wrapper_impl {
 pub(crate) use generate_wrapper_impl;
 
 pub(crate) mod prelude {
-    pub use async_trait::async_trait;
     pub use serde_json::Value;
 
     pub(crate) use crate::{
         error::Result,
-        peripheral::Peripheral,
-        wrappers::{
-            generate_wrapped_fn, generate_wrapper_impl, shared::color::Color, IntoWrappedPeripheral,
-        },
+        peripheral::{Peripheral, PeripheralWrapper},
+        wrappers::{generate_wrapped_fn, generate_wrapper_impl, shared::color::Color},
     };
 }
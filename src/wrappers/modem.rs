@@ -0,0 +1,161 @@
+use crate::{
+    event::{Event, EventReceiver},
+    peripheral::PeripheralCallResult,
+    wrappers::{lua_compat::LuaVec, prelude::*},
+};
+
+generate_wrapper_impl!(Modem = "modem");
+
+/// A message received on an open channel, from the `modem_message` event.
+#[derive(Debug, Clone)]
+pub struct ModemMessage {
+    /// The name of the modem that received the message.
+    pub side: String,
+    pub channel: u16,
+    pub reply_channel: u16,
+    pub payload: Value,
+    /// The distance to the sender, only available on wireless modems.
+    pub distance: Option<f64>,
+}
+
+impl ModemMessage {
+    pub fn from_event(event: &Event) -> Result<Self> {
+        Ok(Self {
+            side: event.param(0)?,
+            channel: event.param(1)?,
+            reply_channel: event.param(2)?,
+            payload: event.param(3)?,
+            distance: event.param(4)?,
+        })
+    }
+}
+
+/// Receives the messages for a single modem.
+#[derive(Debug)]
+pub struct ModemReceiver {
+    side: String,
+    events: EventReceiver,
+}
+
+impl ModemReceiver {
+    pub async fn recv(&mut self) -> Result<ModemMessage> {
+        loop {
+            let event = self.events.recv().await?;
+            let message = ModemMessage::from_event(&event)?;
+            if message.side == self.side {
+                return Ok(message);
+            }
+        }
+    }
+
+    pub fn into_stream(self) -> impl futures_util::Stream<Item = ModemMessage> + Send + Unpin {
+        Box::pin(futures_util::stream::unfold(self, |mut rx| async move {
+            rx.recv().await.ok().map(|message| (message, rx))
+        }))
+    }
+}
+
+impl<'a> Modem<'a> {
+    pub fn address(&self) -> &str {
+        self.inner.address()
+    }
+
+    generate_wrapped_fn!(open -> void = |channel: u16| => open(channel));
+
+    generate_wrapped_fn!(
+        is_open -> bool = |channel: u16| => isOpen(channel);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    generate_wrapped_fn!(close -> void = |channel: u16| => close(channel));
+
+    generate_wrapped_fn!(close_all -> void = | | => closeAll(Value::Null));
+
+    pub async fn transmit(
+        &self,
+        channel: u16,
+        reply_channel: u16,
+        payload: impl serde::Serialize,
+    ) -> Result<()> {
+        let payload = serde_json::to_value(payload)?;
+        self.inner
+            .call_method("transmit", (channel, reply_channel, payload))
+            .await?;
+
+        Ok(())
+    }
+
+    generate_wrapped_fn!(
+        is_wireless -> bool = | | => isWireless(Value::Null);
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    /// Subscribes to the messages received by this modem on any open channel.
+    pub async fn messages(&self) -> Result<ModemReceiver> {
+        Ok(ModemReceiver {
+            side: self.inner.address.clone(),
+            events: self.inner.computer.subscribe("modem_message").await?,
+        })
+    }
+
+    // the following methods are only available on wired modems
+
+    pub async fn get_names_remote(&self) -> Result<Vec<String>> {
+        let names: LuaVec<String> = self
+            .inner
+            .call_method_with("getNamesRemote", Value::Null)
+            .await?;
+
+        Ok(names.0)
+    }
+
+    generate_wrapped_fn!(
+        is_present_remote -> bool = |name: impl ToString| => isPresentRemote(name.to_string());
+        [Value::Bool(b)] => Ok(*b)
+    );
+
+    pub async fn get_type_remote(&self, name: impl ToString) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getTypeRemote", name.to_string())
+            .await
+    }
+
+    pub async fn has_type_remote(&self, name: impl ToString, ty: impl ToString) -> Result<bool> {
+        Ok(self
+            .inner
+            .call_method_with_optional("hasTypeRemote", (name.to_string(), ty.to_string()))
+            .await?
+            .unwrap_or(false))
+    }
+
+    pub async fn get_methods_remote(&self, name: impl ToString) -> Result<Option<Vec<String>>> {
+        let methods: Option<LuaVec<String>> = self
+            .inner
+            .call_method_with_optional("getMethodsRemote", name.to_string())
+            .await?;
+
+        Ok(methods.map(|methods| methods.0))
+    }
+
+    pub async fn call_remote(
+        &self,
+        name: impl ToString,
+        method: impl ToString,
+        args: Vec<Value>,
+    ) -> PeripheralCallResult {
+        let mut all_args = vec![
+            Value::from(name.to_string()),
+            Value::from(method.to_string()),
+        ];
+        all_args.extend(args);
+
+        self.inner.call_method("callRemote", all_args).await
+    }
+
+    /// The name of this computer on the wired network, if the modem is enabled.
+    pub async fn get_name_local(&self) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getNameLocal", Value::Null)
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use super::prelude::*;
+use crate::{computer::Computer, Server};
+
+generate_wrapper_impl!(RemoteComputer = "computer");
+
+impl<'a> RemoteComputer<'a> {
+    generate_wrapped_fn!(turn_on -> void = | | => turnOn(Value::Null));
+    generate_wrapped_fn!(shutdown -> void = | | => shutdown(Value::Null));
+    generate_wrapped_fn!(reboot -> void = | | => reboot(Value::Null));
+
+    pub async fn get_id(&self) -> Result<u64> {
+        self.inner.call_method_with("getID", Value::Null).await
+    }
+
+    pub async fn is_on(&self) -> Result<bool> {
+        self.inner.call_method_with("isOn", Value::Null).await
+    }
+
+    pub async fn get_label(&self) -> Result<Option<String>> {
+        self.inner
+            .call_method_with_optional("getLabel", Value::Null)
+            .await
+    }
+
+    /// Turns the computer on and waits for it to connect to `server` as a worker, which
+    /// requires the worker to be started on boot (i.e. from `startup.lua`).
+    ///
+    /// If the computer is already connected it is returned right away. A computer that is
+    /// already on but not running the worker will not be rebooted, and will time out.
+    pub async fn boot_and_connect(&self, server: &Server, timeout: Duration) -> Result<Computer> {
+        let id = self.get_id().await?;
+        self.turn_on().await?;
+
+        match tokio::time::timeout(timeout, server.wait_for_connection_with_id(id)).await {
+            Ok(computer) => computer,
+            Err(_) => crate::debug_feature!(Err(crate::error::Error::ConnectionTimeout(id))),
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use std::ops::{BitAnd, BitOr, BitOrAssign, Not, Sub};
+
 use serde_json::Value;
 use thiserror::Error;
 
 macro_rules! define_colors {
     ($(
-        $name:ident = $value:expr
+        $name:ident = $value:expr, $blit:literal, $rgb:literal
     ),*) => {
-        #[derive(Debug, Clone, Copy)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[serde(try_from = "u64", into = "u64")]
         #[repr(u64)]
         pub enum Color {
             $($name = $value),*
@@ -21,6 +24,27 @@ macro_rules! define_colors {
             pub fn into_u64(self) -> u64 {
                 self.into()
             }
+
+            /// The hexadecimal digit used to represent this color in `blit` strings.
+            pub fn to_blit_char(self) -> char {
+                match self {
+                    $(Color::$name => $blit,)*
+                }
+            }
+
+            pub fn from_blit_char(c: char) -> Result<Self, TryFromColorError> {
+                match c.to_ascii_lowercase() {
+                    $($blit => Ok(Color::$name),)*
+                    _ => Err(TryFromColorError::InvalidBlitChar(c)),
+                }
+            }
+
+            /// The RGB value of this color in the default palette, as `0xRRGGBB`.
+            pub fn default_rgb(self) -> u32 {
+                match self {
+                    $(Color::$name => $rgb,)*
+                }
+            }
         }
 
         impl TryFrom<u64> for Color {
@@ -40,25 +64,52 @@ macro_rules! define_colors {
 pub enum TryFromColorError {
     #[error("invalid color value: {0}")]
     InvalidColorValue(u64),
+    #[error("invalid blit character: {0:?}")]
+    InvalidBlitChar(char),
 }
 
 define_colors! {
-    White = 1,
-    Orange = 2,
-    Magenta = 4,
-    LightBlue = 8,
-    Yellow = 16,
-    Lime = 32,
-    Pink = 64,
-    Gray = 128,
-    LightGray = 256,
-    Cyan = 512,
-    Purple = 1024,
-    Blue = 2048,
-    Brown = 4096,
-    Green = 8192,
-    Red = 16384,
-    Black = 32768
+    White = 1, '0', 0xF0F0F0,
+    Orange = 2, '1', 0xF2B233,
+    Magenta = 4, '2', 0xE57FD8,
+    LightBlue = 8, '3', 0x99B2F2,
+    Yellow = 16, '4', 0xDEDE6C,
+    Lime = 32, '5', 0x7FCC19,
+    Pink = 64, '6', 0xF2B2CC,
+    Gray = 128, '7', 0x4C4C4C,
+    LightGray = 256, '8', 0x999999,
+    Cyan = 512, '9', 0x4C99B2,
+    Purple = 1024, 'a', 0xB266E5,
+    Blue = 2048, 'b', 0x3366CC,
+    Brown = 4096, 'c', 0x7F664C,
+    Green = 8192, 'd', 0x57A64E,
+    Red = 16384, 'e', 0xCC4C4C,
+    Black = 32768, 'f', 0x111111
+}
+
+impl Color {
+    /// Finds the color in the default palette that is closest to `rgb` (as `0xRRGGBB`).
+    pub fn nearest(rgb: u32) -> Color {
+        let channels = |rgb: u32| {
+            [
+                ((rgb >> 16) & 0xff) as i32,
+                ((rgb >> 8) & 0xff) as i32,
+                (rgb & 0xff) as i32,
+            ]
+        };
+        let target = channels(rgb);
+
+        Color::colors()
+            .into_iter()
+            .min_by_key(|color| {
+                channels(color.default_rgb())
+                    .iter()
+                    .zip(target.iter())
+                    .map(|(a, b)| (a - b) * (a - b))
+                    .sum::<i32>()
+            })
+            .unwrap()
+    }
 }
 
 impl From<Color> for u64 {
@@ -72,3 +123,213 @@ impl From<Color> for Value {
         Value::Number((color as u64).into())
     }
 }
+
+impl BitOr for Color {
+    type Output = ColorSet;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        ColorSet::from(self) | rhs
+    }
+}
+
+/// A set of colors, stored as the bitmask used by `colors.combine` and bundled cables.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ColorSet(u64);
+
+impl ColorSet {
+    const MASK: u64 = 0xffff;
+
+    pub fn new() -> Self {
+        Self(0)
+    }
+
+    pub fn all() -> Self {
+        Self(Self::MASK)
+    }
+
+    /// Creates a set from a bitmask, ignoring any bits that are not colors.
+    pub fn from_bits_truncate(bits: u64) -> Self {
+        Self(bits & Self::MASK)
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn contains(self, color: Color) -> bool {
+        self.0 & color.into_u64() != 0
+    }
+
+    pub fn insert(&mut self, color: Color) {
+        self.0 |= color.into_u64();
+    }
+
+    pub fn remove(&mut self, color: Color) {
+        self.0 &= !color.into_u64();
+    }
+
+    pub fn iter(self) -> ColorSetIter {
+        ColorSetIter(self.0)
+    }
+
+    pub fn union(self, other: ColorSet) -> ColorSet {
+        Self(self.0 | other.0)
+    }
+
+    pub fn intersection(self, other: ColorSet) -> ColorSet {
+        Self(self.0 & other.0)
+    }
+
+    pub fn difference(self, other: ColorSet) -> ColorSet {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn is_subset(self, other: ColorSet) -> bool {
+        self.intersection(other) == self
+    }
+}
+
+impl<T: Into<ColorSet>> BitOr<T> for ColorSet {
+    type Output = ColorSet;
+
+    fn bitor(self, rhs: T) -> Self::Output {
+        self.union(rhs.into())
+    }
+}
+
+impl<T: Into<ColorSet>> BitOrAssign<T> for ColorSet {
+    fn bitor_assign(&mut self, rhs: T) {
+        *self = self.union(rhs.into());
+    }
+}
+
+impl<T: Into<ColorSet>> BitAnd<T> for ColorSet {
+    type Output = ColorSet;
+
+    fn bitand(self, rhs: T) -> Self::Output {
+        self.intersection(rhs.into())
+    }
+}
+
+impl<T: Into<ColorSet>> Sub<T> for ColorSet {
+    type Output = ColorSet;
+
+    fn sub(self, rhs: T) -> Self::Output {
+        self.difference(rhs.into())
+    }
+}
+
+impl Not for ColorSet {
+    type Output = ColorSet;
+
+    fn not(self) -> Self::Output {
+        Self::all().difference(self)
+    }
+}
+
+impl IntoIterator for ColorSet {
+    type Item = Color;
+    type IntoIter = ColorSetIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterates the colors in a [`ColorSet`] from lowest to highest value.
+#[derive(Debug, Clone)]
+pub struct ColorSetIter(u64);
+
+impl Iterator for ColorSetIter {
+    type Item = Color;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let lowest = self.0 & self.0.wrapping_neg();
+        self.0 &= !lowest;
+
+        lowest.try_into().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl Extend<Color> for ColorSet {
+    fn extend<T: IntoIterator<Item = Color>>(&mut self, iter: T) {
+        for color in iter {
+            self.insert(color);
+        }
+    }
+}
+
+impl From<Color> for ColorSet {
+    fn from(color: Color) -> Self {
+        Self(color.into_u64())
+    }
+}
+
+impl FromIterator<Color> for ColorSet {
+    fn from_iter<T: IntoIterator<Item = Color>>(iter: T) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl From<ColorSet> for Value {
+    fn from(set: ColorSet) -> Self {
+        Value::Number(set.0.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union() {
+        let set = Color::Red | Color::Blue;
+        assert_eq!(set.bits(), 16384 | 2048);
+        assert_eq!(set | Color::Red, set);
+        assert_eq!((set | Color::White).len(), 3);
+        assert_eq!(set.union(ColorSet::new()), set);
+    }
+
+    #[test]
+    fn difference() {
+        let set = Color::Red | Color::Blue | Color::Green;
+        assert_eq!(set - Color::Blue, Color::Red | Color::Green);
+        assert_eq!(set - Color::White, set);
+        assert!(set.difference(set).is_empty());
+        assert_eq!(!set, ColorSet::all() - set);
+        assert_eq!((!set).len(), 13);
+    }
+
+    #[test]
+    fn contains() {
+        let mut set = Color::Red | Color::Blue;
+        assert!(set.contains(Color::Red));
+        assert!(!set.contains(Color::Black));
+
+        set.insert(Color::Black);
+        set.remove(Color::Red);
+        assert!(set.contains(Color::Black));
+        assert!(!set.contains(Color::Red));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Color::Blue, Color::Black]);
+        assert!(ColorSet::from(Color::Blue).is_subset(set));
+    }
+}
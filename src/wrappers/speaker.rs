@@ -0,0 +1,107 @@
+use futures_util::{Stream, StreamExt};
+
+use super::prelude::*;
+
+mod instrument;
+pub use instrument::*;
+mod note_pitch;
+pub use note_pitch::*;
+pub mod dfpwm;
+
+generate_wrapper_impl!(Speaker = "speaker");
+
+/// The maximum number of samples that can be passed to [`Speaker::play_audio`] at once.
+pub const MAX_AUDIO_CHUNK: usize = 128 * 1024;
+
+/// The chunk size used by [`Speaker::play_audio_stream`], about a third of a second of audio.
+pub const DEFAULT_AUDIO_CHUNK: usize = 16 * 1024;
+
+/// The sample rate of audio played by speakers.
+pub const SAMPLE_RATE: u32 = 48000;
+
+impl<'a> Speaker<'a> {
+    /// Plays a note block note, returning false if too many notes were played this tick.
+    ///
+    /// `volume` is between 0.0 and 3.0.
+    pub async fn play_note(
+        &self,
+        instrument: Instrument,
+        volume: f64,
+        pitch: NotePitch,
+    ) -> Result<bool> {
+        self.inner
+            .call_method_with("playNote", (instrument, volume, pitch))
+            .await
+    }
+
+    /// Plays any sound by its resource location (i.e. `minecraft:entity.creeper.primed`),
+    /// returning false if another sound was already started this tick.
+    ///
+    /// `volume` is between 0.0 and 3.0, `pitch` is between 0.5 and 2.0.
+    pub async fn play_sound(&self, name: impl ToString, volume: f64, pitch: f64) -> Result<bool> {
+        self.inner
+            .call_method_with("playSound", (name.to_string(), volume, pitch))
+            .await
+    }
+
+    generate_wrapped_fn!(stop -> void = | | => stop(Value::Null));
+
+    /// Queues signed 8-bit PCM samples at 48kHz to be played, returning false if the
+    /// speaker's buffer is full. At most [`MAX_AUDIO_CHUNK`] samples may be queued at once.
+    pub async fn play_audio(&self, samples: &[i8], volume: Option<f64>) -> Result<bool> {
+        self.inner
+            .call_method_with("playAudio", (samples.to_vec(), volume))
+            .await
+    }
+
+    /// Plays every chunk of samples from `chunks`, waiting for the speaker's buffer to
+    /// empty (the `speaker_audio_empty` event) whenever it is full.
+    ///
+    /// Chunks larger than [`MAX_AUDIO_CHUNK`] are split up.
+    pub async fn play_audio_stream<S>(&self, chunks: S, volume: Option<f64>) -> Result<()>
+    where
+        S: Stream<Item = Vec<i8>> + Unpin,
+    {
+        let mut events = self.inner.computer.subscribe("speaker_audio_empty").await?;
+        let mut chunks = chunks;
+
+        while let Some(chunk) = chunks.next().await {
+            for part in chunk.chunks(MAX_AUDIO_CHUNK) {
+                while !self.play_audio(part, volume).await? {
+                    loop {
+                        let event = events.recv().await?;
+                        if event.param::<String>(0)? == self.inner.address {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Plays all of `samples`, see [`Speaker::play_audio_stream`].
+    pub async fn play_audio_all(&self, samples: &[i8], volume: Option<f64>) -> Result<()> {
+        let chunks = samples
+            .chunks(DEFAULT_AUDIO_CHUNK)
+            .map(|chunk| chunk.to_vec())
+            .collect::<Vec<_>>();
+
+        self.play_audio_stream(futures_util::stream::iter(chunks), volume)
+            .await
+    }
+}
+
+/// Converts unsigned 8-bit PCM (as stored in 8-bit WAV files) to the signed samples speakers play.
+pub fn pcm_from_u8(samples: &[u8]) -> Vec<i8> {
+    samples
+        .iter()
+        .map(|sample| (*sample as i16 - 128) as i8)
+        .collect()
+}
+
+/// Converts signed 16-bit PCM (as stored in 16-bit WAV files) to the signed samples speakers play.
+pub fn pcm_from_i16(samples: &[i16]) -> Vec<i8> {
+    samples.iter().map(|sample| (*sample >> 8) as i8).collect()
+}
@@ -0,0 +1,185 @@
+//! A port of the DFPWM1a codec from `cc.audio.dfpwm`, so audio can be converted to and from
+//! the format used by CC on the host.
+//!
+//! Both the encoder and decoder keep their state between calls, so a stream can be converted
+//! in chunks as long as the same encoder or decoder is used for the whole stream.
+
+const PREC: i32 = 10;
+const LPF_STRENGTH: i32 = 140;
+
+#[derive(Debug, Clone, Default)]
+struct Predictor {
+    charge: i32,
+    strength: i32,
+    previous_bit: bool,
+}
+
+impl Predictor {
+    fn predict(&mut self, current_bit: bool) -> i32 {
+        let target = if current_bit { 127 } else { -128 };
+
+        let mut next_charge =
+            self.charge + ((self.strength * (target - self.charge) + (1 << (PREC - 1))) >> PREC);
+        if next_charge == self.charge && next_charge != target {
+            next_charge += if current_bit { 1 } else { -1 };
+        }
+
+        let same = current_bit == self.previous_bit;
+        let z = if same { (1 << PREC) - 1 } else { 0 };
+        let mut next_strength = self.strength;
+        if next_strength != z {
+            next_strength += if same { 1 } else { -1 };
+        }
+        if next_strength < 2 << (PREC - 8) {
+            next_strength = 2 << (PREC - 8);
+        }
+
+        self.charge = next_charge;
+        self.strength = next_strength;
+        self.previous_bit = current_bit;
+
+        self.charge
+    }
+}
+
+/// Encodes signed 8-bit PCM samples into DFPWM, 8 samples per byte.
+#[derive(Debug, Clone, Default)]
+pub struct DfpwmEncoder {
+    predictor: Predictor,
+    previous_charge: i32,
+}
+
+impl DfpwmEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encodes `samples`. If the number of samples is not a multiple of 8,
+    /// the last byte is padded with silence.
+    pub fn encode(&mut self, samples: &[i8]) -> Vec<u8> {
+        samples
+            .chunks(8)
+            .map(|chunk| {
+                let mut byte = 0u8;
+                for i in 0..8 {
+                    let input = chunk.get(i).copied().unwrap_or(0) as i32;
+                    let current_bit = input > self.previous_charge
+                        || (input == self.previous_charge && self.previous_charge == 127);
+                    byte = (byte >> 1) | if current_bit { 0x80 } else { 0 };
+                    self.previous_charge = self.predictor.predict(current_bit);
+                }
+                byte
+            })
+            .collect()
+    }
+}
+
+/// Decodes DFPWM into signed 8-bit PCM samples, 8 samples per byte.
+#[derive(Debug, Clone, Default)]
+pub struct DfpwmDecoder {
+    predictor: Predictor,
+    low_pass_charge: i32,
+    previous_charge: i32,
+    previous_bit: bool,
+}
+
+impl DfpwmDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn decode(&mut self, data: &[u8]) -> Vec<i8> {
+        let mut samples = Vec::with_capacity(data.len() * 8);
+
+        for byte in data {
+            let mut byte = *byte;
+            for _ in 0..8 {
+                let current_bit = byte & 1 != 0;
+                let charge = self.predictor.predict(current_bit);
+
+                let antijerk = if current_bit != self.previous_bit {
+                    (charge + self.previous_charge + 1) >> 1
+                } else {
+                    charge
+                };
+
+                self.previous_charge = charge;
+                self.previous_bit = current_bit;
+
+                self.low_pass_charge +=
+                    ((antijerk - self.low_pass_charge) * LPF_STRENGTH + 0x80) >> 8;
+                samples.push(self.low_pass_charge.clamp(-128, 127) as i8);
+
+                byte >>= 1;
+            }
+        }
+
+        samples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(len: usize) -> Vec<i8> {
+        (0..len)
+            .map(|i| ((i as f64 * 440.0 * std::f64::consts::TAU / 48000.0).sin() * 100.0) as i8)
+            .collect()
+    }
+
+    #[test]
+    fn encodes_known_vectors() {
+        // silence alternates between the two bits, and a full signal is all ones
+        assert_eq!(DfpwmEncoder::new().encode(&[0; 16]), [0xaa, 0xaa]);
+        assert_eq!(DfpwmEncoder::new().encode(&[127; 16]), [0xff, 0xff]);
+        // a partial chunk is padded with silence
+        assert_eq!(DfpwmEncoder::new().encode(&[0; 3]), [0xaa]);
+    }
+
+    #[test]
+    fn decodes_silence_to_silence() {
+        let samples = DfpwmDecoder::new().decode(&[0xaa; 64]);
+        assert_eq!(samples.len(), 512);
+        assert!(
+            samples.iter().all(|sample| sample.abs() <= 2),
+            "{samples:?}"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let samples = sine(48000);
+        let decoded = DfpwmDecoder::new().decode(&DfpwmEncoder::new().encode(&samples));
+        assert_eq!(decoded.len(), samples.len());
+
+        // lossy, but the decoded wave should follow the original closely
+        let error = samples
+            .iter()
+            .zip(&decoded)
+            .map(|(a, b)| (*a as i32 - *b as i32).abs())
+            .sum::<i32>() as f64
+            / samples.len() as f64;
+        assert!(error < 10.0, "average error {error}");
+    }
+
+    #[test]
+    fn keeps_state_between_chunks() {
+        let samples = sine(4096);
+        let whole = DfpwmEncoder::new().encode(&samples);
+
+        let mut encoder = DfpwmEncoder::new();
+        let chunked: Vec<u8> = samples
+            .chunks(1024)
+            .flat_map(|chunk| encoder.encode(chunk))
+            .collect();
+        assert_eq!(whole, chunked);
+
+        let mut decoder = DfpwmDecoder::new();
+        let chunked: Vec<i8> = whole
+            .chunks(100)
+            .flat_map(|chunk| decoder.decode(chunk))
+            .collect();
+        assert_eq!(DfpwmDecoder::new().decode(&whole), chunked);
+    }
+}
@@ -0,0 +1,40 @@
+use serde_json::Value;
+use thiserror::Error;
+
+/// The pitch of a note in semitones, between 0 and 24 inclusive (the same as a note block).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8")]
+pub struct NotePitch(pub(super) u8);
+
+impl NotePitch {
+    pub const MIN: NotePitch = NotePitch(0);
+    pub const MAX: NotePitch = NotePitch(24);
+
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TryParseNotePitchError {
+    #[error("Note pitch out of range. Must be between 0 and 24")]
+    OutOfRange,
+}
+
+impl TryFrom<u8> for NotePitch {
+    type Error = TryParseNotePitchError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        if value > 24 {
+            return Err(TryParseNotePitchError::OutOfRange);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<NotePitch> for Value {
+    fn from(value: NotePitch) -> Self {
+        value.0.into()
+    }
+}